@@ -0,0 +1,282 @@
+//! A blocking client, for scripts and tools that do not use async code. Requires the `blocking`
+//! feature.
+//!
+//! The types mirror the async ones and wrap them: every method drives the async request to
+//! completion on the calling thread. The blocking client uses the blocking client of reqwest if
+//! `reqwest-client` is enabled, and surf otherwise, so no async runtime needs to be running. Do
+//! not call it from within async code, as it blocks the executor thread.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use misp_client::MispResult;
+//! use misp_client::blocking::MISP;
+//!
+//! # fn main() -> MispResult<()> {
+//! let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+//! let events = misp.events().list().with_tag("tlp:white").limit(3).retrieve()?;
+//! for event in events {
+//!     println!("{}", event.info());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::requests::action::ActionResponse;
+use crate::requests::tag_query::TagQuery;
+use crate::{MISPBuilder, MispResult};
+use chrono::{Date, DateTime, Utc};
+use futures::executor::{block_on, block_on_stream};
+use misp_types::analysis::Analysis;
+use misp_types::attribute_type::{AttributeCategory, AttributeType};
+use misp_types::event::{
+    EventFull, EventIdentifier, EventUpdate, GenericEventIdentifier, NewEvent,
+};
+use misp_types::organization::GenericOrganizationIdentifier;
+use misp_types::server_info::ServerInfo;
+use misp_types::threat_level::ThreatLevel;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// A blocking MISP client. See [`crate::MISP`](../struct.MISP.html) for the async client.
+#[derive(Debug, Clone)]
+pub struct MISP {
+    inner: crate::MISP,
+}
+
+impl MISP {
+    /// Creates a new MISP Client given a base URL and an authorization token.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base URL is invalid or if neither `surf-client` nor `reqwest-client` is
+    /// enabled. Use [`MISP::builder`](#method.builder) to handle the error or to configure
+    /// certificates, a proxy or timeouts.
+    pub fn new(base_url: impl AsRef<str>, auth_token: impl Into<String>) -> Self {
+        Self::builder(base_url.as_ref(), auth_token)
+            .build_blocking()
+            .expect("Invalid MISP client configuration")
+    }
+
+    /// Starts the configuration of a client. The blocking client is created with
+    /// [`build_blocking`](../struct.MISPBuilder.html#method.build_blocking):
+    ///
+    /// ```no_run
+    /// # use misp_client::MispResult;
+    /// # use std::time::Duration;
+    /// # fn main() -> MispResult<()> {
+    /// let misp = misp_client::blocking::MISP::builder("https://misp.demo.com", "VERYSECRETTOKEN")
+    ///     .timeout(Duration::from_secs(60))
+    ///     .build_blocking()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder(base_url: impl Into<String>, auth_token: impl Into<String>) -> MISPBuilder {
+        MISPBuilder::new(base_url, auth_token)
+    }
+
+    /// The async client this client wraps, e.g. to use the APIs the blocking client does not
+    /// cover.
+    pub fn async_client(&self) -> &crate::MISP {
+        &self.inner
+    }
+
+    pub fn server_info(&self) -> MispResult<ServerInfo> {
+        block_on(self.inner.server_info())
+    }
+
+    pub fn events(&self) -> EventsApi<'_> {
+        EventsApi { misp_client: self }
+    }
+}
+
+impl From<crate::MISP> for MISP {
+    fn from(inner: crate::MISP) -> Self {
+        MISP { inner }
+    }
+}
+
+/// EventsApi is bound to the lifetime of the MISP client instance
+pub struct EventsApi<'a> {
+    misp_client: &'a MISP,
+}
+
+impl<'a> EventsApi<'a> {
+    pub fn list(&self) -> EventListRequest<'a> {
+        EventListRequest {
+            inner: self.misp_client.inner.events().list(),
+        }
+    }
+
+    pub fn get(&self, event: impl Into<GenericEventIdentifier>) -> EventRequest<'a> {
+        EventRequest {
+            inner: self.misp_client.inner.events().get(event),
+        }
+    }
+
+    /// Creates a new event, including its attributes, objects and tags, on the server.
+    pub fn add(&self, event: NewEvent) -> MispResult<EventFull> {
+        block_on(self.misp_client.inner.events().add(event).send())
+    }
+
+    /// Edits an existing event. Only the fields that are set in `update` are changed on the
+    /// server.
+    pub fn update(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+        update: EventUpdate,
+    ) -> MispResult<EventFull> {
+        block_on(self.misp_client.inner.events().update(event, update).send())
+    }
+}
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct EventRequest<'a> {
+    inner: crate::requests::event::EventRequest<'a>,
+}
+
+impl EventRequest<'_> {
+    pub fn retrieve(&mut self) -> MispResult<EventFull> {
+        block_on(self.inner.retrieve())
+    }
+
+    pub fn id(&mut self) -> MispResult<EventIdentifier> {
+        block_on(self.inner.id())
+    }
+
+    pub fn uuid(&mut self) -> MispResult<Uuid> {
+        block_on(self.inner.uuid())
+    }
+
+    /// Deletes the event permanently from the server, including all its attributes and objects.
+    pub fn delete(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.delete())
+    }
+
+    /// Publishes the event without sending e-mail alerts to the users of the instance.
+    pub fn publish(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.publish())
+    }
+
+    /// Publishes the event and sends e-mail alerts to all the users that are eligible to see it.
+    pub fn publish_with_alert(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.publish_with_alert())
+    }
+
+    pub fn unpublish(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.unpublish())
+    }
+
+    /// Locks the event, so that it is not modified by synchronisations anymore.
+    pub fn lock(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.lock())
+    }
+
+    pub fn unlock(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.unlock())
+    }
+
+    /// Stops correlating the attributes of this event with the attributes of other events.
+    pub fn disable_correlation(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.disable_correlation())
+    }
+
+    pub fn enable_correlation(&mut self) -> MispResult<ActionResponse> {
+        block_on(self.inner.enable_correlation())
+    }
+}
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct EventListRequest<'a> {
+    inner: crate::requests::event_list::EventListRequest<'a>,
+}
+
+/// Forwards filters to the async request, returning the blocking one so that calls can be chained
+/// up to `retrieve`.
+macro_rules! forward_filters {
+    ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*);)*) => {
+        $(
+            #[doc = concat!(
+                "See [`EventListRequest::", stringify!($name),
+                "`](../requests/event_list/struct.EventListRequest.html#method.", stringify!($name), ")."
+            )]
+            pub fn $name(&mut self $(, $arg: $ty)*) -> &mut Self {
+                self.inner.$name($($arg),*);
+                self
+            }
+        )*
+    };
+}
+
+impl<'a> EventListRequest<'a> {
+    /// Downloads all the events matching the set filters
+    pub fn retrieve(&mut self) -> MispResult<Vec<EventFull>> {
+        block_on(self.inner.retrieve())
+    }
+
+    /// Downloads the events matching the set filters page by page and returns them one at a
+    /// time. See [`EventListRequest::stream`](../requests/event_list/struct.EventListRequest.html#method.stream).
+    pub fn iter(&self) -> impl Iterator<Item = MispResult<EventFull>> + 'a {
+        block_on_stream(self.inner.stream())
+    }
+
+    forward_filters! {
+        fn page_size(&mut self, page_size: u64);
+        fn concurrency(&mut self, concurrency: usize);
+        fn from_organization(&mut self, organization: impl Into<GenericOrganizationIdentifier>);
+        fn containing_info(&mut self, search: impl AsRef<str>);
+        fn with_exact_info(&mut self, search: impl Into<String>);
+        fn after(&mut self, date: Date<Utc>);
+        fn before(&mut self, date: Date<Utc>);
+        fn limit(&mut self, limit: u64);
+        fn with_value(&mut self, value: impl Into<String>);
+        fn containing_value(&mut self, search: impl AsRef<str>);
+        fn with_attribute_kind(&mut self, kind: AttributeType);
+        fn with_attribute_category(&mut self, category: AttributeCategory);
+        fn matching_tags(&mut self, query: TagQuery);
+        fn with_tag(&mut self, name: impl Into<String>);
+        fn with_any_tag(&mut self, names: impl IntoIterator<Item = impl Into<String>>);
+        fn without_tag(&mut self, name: impl Into<String>);
+        fn with_threat_level(&mut self, threat_level: ThreatLevel);
+        fn with_analysis(&mut self, analysis: Analysis);
+        fn published(&mut self, published: bool);
+        fn to_ids(&mut self, to_ids: bool);
+        fn last(&mut self, duration: Duration);
+        fn modified_after(&mut self, timestamp: DateTime<Utc>);
+        fn modified_before(&mut self, timestamp: DateTime<Utc>);
+        fn published_after(&mut self, timestamp: DateTime<Utc>);
+        fn published_before(&mut self, timestamp: DateTime<Utc>);
+        fn with_id(&mut self, event: EventIdentifier);
+        fn with_uuid(&mut self, uuid: Uuid);
+        fn with_attachments(&mut self);
+        fn include_event_tags(&mut self);
+        fn include_context(&mut self);
+        fn enforce_warninglist(&mut self);
+        fn sharing_group_reference_only(&mut self);
+        fn include_deleted(&mut self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MISP;
+    use crate::transport::fake::{response, FakeTransport};
+
+    #[test]
+    fn blocking_calls() {
+        let transport = FakeTransport::new(|request| match request.url().path() {
+            "/events/restSearch" => response(200, r#"{"response": []}"#),
+            _ => response(
+                200,
+                r#"{"version": "2.4.130", "perm_sync": false, "perm_sighting": true}"#,
+            ),
+        });
+        let misp = MISP::from(transport.client());
+        assert_eq!("2.4.130", misp.server_info().unwrap().version);
+
+        let mut list = misp.events().list();
+        list.with_tag("tlp:white").page_size(10);
+        assert_eq!(0, list.iter().count());
+        assert!(list.retrieve().unwrap().is_empty());
+        assert_eq!(3, transport.requests().len());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::client::MISP;
+use crate::error::{MispError, MispResult};
+use crate::retry::RetryPolicy;
+use crate::transport::HttpTransport;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Configures and creates a [`MISP`](struct.MISP.html) client, e.g. for servers behind a proxy or
+/// with certificates signed by an internal certificate authority.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use misp_client::{MISPBuilder, MispResult};
+/// # use std::time::Duration;
+/// # fn main() -> MispResult<()> {
+/// let misp = MISPBuilder::new("https://misp.internal.example", "VERYSECRETTOKEN")
+///     .ca_certificate("/etc/ssl/certs/internal-ca.pem")
+///     .proxy("http://proxy.internal.example:3128")
+///     .timeout(Duration::from_secs(120))
+///     .user_agent("threat-sync/1.2")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MISPBuilder {
+    base_url: String,
+    auth_token: String,
+    pub(crate) ca_certificate: Option<PathBuf>,
+    pub(crate) client_certificate: Option<(PathBuf, PathBuf)>,
+    pub(crate) accept_invalid_certificates: bool,
+    pub(crate) proxy: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    user_agent: String,
+    headers: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+    transport: Option<Arc<dyn HttpTransport>>,
+}
+
+/// Checks a header against RFC 7230: names are tokens, values are visible ASCII characters,
+/// spaces and tabs.
+fn is_valid_header(name: &str, value: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        && value
+            .bytes()
+            .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+}
+
+impl MISPBuilder {
+    /// Starts the configuration of a client given the base URL of the server and an
+    /// authorization token.
+    pub fn new(base_url: impl Into<String>, auth_token: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            auth_token: auth_token.into(),
+            ca_certificate: None,
+            client_certificate: None,
+            accept_invalid_certificates: false,
+            proxy: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: "rs_misp".into(),
+            headers: Vec::new(),
+            retry_policy: RetryPolicy::default(),
+            transport: None,
+        }
+    }
+
+    /// Trusts the certificate authorities of a PEM bundle instead of the ones of the system.
+    pub fn ca_certificate(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.ca_certificate = Some(path.into());
+        self
+    }
+
+    /// Authenticates the client with a certificate (mutual TLS). Both files are PEM encoded.
+    pub fn client_certificate(
+        &mut self,
+        certificate: impl Into<PathBuf>,
+        private_key: impl Into<PathBuf>,
+    ) -> &mut Self {
+        self.client_certificate = Some((certificate.into(), private_key.into()));
+        self
+    }
+
+    /// Disables the verification of the server's certificate and host name.
+    ///
+    /// Anyone between the client and the server can read and change the traffic, including the
+    /// authorization token. Only use this for test instances.
+    pub fn danger_accept_invalid_certificates(&mut self, accept: bool) -> &mut Self {
+        self.accept_invalid_certificates = accept;
+        self
+    }
+
+    /// Sends the requests through a proxy. HTTP (`http://host:port`) and SOCKS
+    /// (`socks5://host:port`) proxies are supported.
+    pub fn proxy(&mut self, proxy: impl Into<String>) -> &mut Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Sets the longest time a request may take, from connecting until the whole response was
+    /// received. By default, requests never time out.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the longest time establishing a connection to the server may take.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Replaces the default `rs_misp` User-Agent.
+    pub fn user_agent(&mut self, user_agent: impl Into<String>) -> &mut Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Adds a header that is sent along with every request. It replaces the default header of the
+    /// same name, e.g. `Accept` or `User-Agent`.
+    pub fn header(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets when failed requests are sent again. See [`RetryPolicy`](struct.RetryPolicy.html) for
+    /// the default behaviour.
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends the requests with a custom [`HttpTransport`](transport/trait.HttpTransport.html)
+    /// instead of the one of the enabled cargo features (see the [`transport`](transport/index.html)
+    /// module). The TLS, proxy and timeout settings are ignored, they have to be applied to the
+    /// transport.
+    pub fn transport(&mut self, transport: impl HttpTransport) -> &mut Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Reqwest is preferred when both transports are enabled: it is only enabled explicitly,
+    /// while surf may still be enabled by the default features.
+    #[allow(unreachable_code)]
+    fn default_transport(&self) -> MispResult<Arc<dyn HttpTransport>> {
+        #[cfg(feature = "reqwest-client")]
+        return Ok(Arc::new(crate::transport::ReqwestTransport::from_builder(
+            self,
+        )?));
+        #[cfg(feature = "surf-client")]
+        return Ok(Arc::new(crate::transport::SurfTransport::from_builder(
+            self,
+        )?));
+        Err(MispError::ConfigurationError(
+            "No HTTP transport: enable the surf-client or reqwest-client feature".into(),
+        ))
+    }
+
+    /// Creates the client. Fails if the base URL, the proxy or a header is invalid.
+    pub fn build(&self) -> MispResult<MISP> {
+        self.build_with(Self::default_transport)
+    }
+
+    /// Creates a [blocking](blocking/index.html) client. Unless a custom transport is set, it uses
+    /// the blocking client of reqwest if `reqwest-client` is enabled, and surf otherwise. Fails if
+    /// neither of them is enabled.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(&self) -> MispResult<crate::blocking::MISP> {
+        self.build_with(Self::default_blocking_transport)
+            .map(crate::blocking::MISP::from)
+    }
+
+    #[cfg(feature = "blocking")]
+    #[allow(unreachable_code)]
+    fn default_blocking_transport(&self) -> MispResult<Arc<dyn HttpTransport>> {
+        #[cfg(feature = "reqwest-client")]
+        return Ok(Arc::new(
+            crate::transport::ReqwestBlockingTransport::from_builder(self)?,
+        ));
+        #[cfg(feature = "surf-client")]
+        return Ok(Arc::new(crate::transport::SurfTransport::from_builder(
+            self,
+        )?));
+        Err(MispError::ConfigurationError(
+            "No HTTP transport: enable the surf-client or reqwest-client feature".into(),
+        ))
+    }
+
+    fn build_with(
+        &self,
+        default_transport: impl FnOnce(&Self) -> MispResult<Arc<dyn HttpTransport>>,
+    ) -> MispResult<MISP> {
+        let mut headers = Vec::new();
+        if !self
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("User-Agent"))
+        {
+            headers.push(("User-Agent".to_string(), self.user_agent.clone()));
+        }
+        headers.extend(self.headers.iter().cloned());
+        if let Some((name, value)) = headers
+            .iter()
+            .find(|(name, value)| !is_valid_header(name, value))
+        {
+            return Err(MispError::ConfigurationError(format!(
+                "Invalid header {}: {}",
+                name, value
+            )));
+        }
+        let mut base_url: Url = self.base_url.parse()?;
+        // Without a trailing slash, the last segment of the path would be replaced by the
+        // endpoints (e.g. `https://host/misp` + `events` = `https://host/events`)
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        let transport = match &self.transport {
+            Some(transport) => transport.clone(),
+            None => default_transport(self)?,
+        };
+        Ok(MISP::from_parts(
+            base_url,
+            self.auth_token.clone(),
+            headers,
+            self.retry_policy.clone(),
+            transport,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MISPBuilder;
+    use crate::MispError;
+
+    #[test]
+    fn invalid_configuration() {
+        assert!(matches!(
+            MISPBuilder::new("not a url", "12345678").build(),
+            Err(MispError::UrlParseError(_))
+        ));
+        assert!(matches!(
+            MISPBuilder::new("https://test.xyz", "12345678")
+                .header("X-Prüfung", "value")
+                .build(),
+            Err(MispError::ConfigurationError(_))
+        ));
+        assert!(MISPBuilder::new("https://test.xyz/misp", "12345678")
+            .proxy("socks5://127.0.0.1:1080")
+            .build()
+            .is_ok());
+    }
+}
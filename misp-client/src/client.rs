@@ -1,13 +1,20 @@
-use surf::http_types::headers::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use futures::io::AsyncReadExt;
+use futures::Stream;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
-use crate::error::MispResult;
-use crate::requests::api::EventsApi;
+use crate::builder::MISPBuilder;
+use crate::error::{MispError, MispResult};
+use crate::json_stream;
+use crate::requests::api::{
+    AttributesApi, EventsApi, GalaxiesApi, ObjectsApi, ProposalsApi, TagsApi,
+};
+use crate::retry::RetryPolicy;
+use crate::transport::{HttpRequest, HttpResponse, HttpTransport, Method};
 use misp_types::server_info::ServerInfo;
 
-#[cfg(feature = "serde")]
 use serde::de::DeserializeOwned;
-#[cfg(feature = "serde")]
 use serde::Serialize;
 
 /// This is the starting point: A MISP client. It us used to connect to a MISP Server.
@@ -34,6 +41,30 @@ use serde::Serialize;
 pub struct MISP {
     base_url: Url,
     auth_token: String,
+    headers: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+    transport: Arc<dyn HttpTransport>,
+}
+
+/// Turns unsuccessful responses into the matching [`MispError`](enum.MispError.html). The body of
+/// successful responses is left untouched.
+async fn check_status(response: HttpResponse) -> MispResult<HttpResponse> {
+    let status = response.status();
+    if (200..300).contains(&status) {
+        return Ok(response);
+    }
+    let retry_after = response
+        .header("Retry-After")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    let body = read_body(response).await?;
+    Err(MispError::from_response(status, &body, retry_after))
+}
+
+async fn read_body(response: HttpResponse) -> MispResult<Vec<u8>> {
+    let mut body = Vec::new();
+    response.into_body().read_to_end(&mut body).await?;
+    Ok(body)
 }
 
 impl MISP {
@@ -58,10 +89,93 @@ impl MISP {
     ///
     /// let misp = MISP::new(base_url, auth_token);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the base URL is invalid. Use [`MISP::builder`](#method.builder) to handle the
+    /// error or to configure certificates, a proxy or timeouts.
     pub fn new(base_url: impl AsRef<str>, auth_token: impl Into<String>) -> Self {
+        Self::builder(base_url.as_ref(), auth_token)
+            .build()
+            .expect("Invalid MISP client configuration")
+    }
+
+    /// Starts the configuration of a client. See [`MISPBuilder`](struct.MISPBuilder.html).
+    pub fn builder(base_url: impl Into<String>, auth_token: impl Into<String>) -> MISPBuilder {
+        MISPBuilder::new(base_url, auth_token)
+    }
+
+    pub(crate) fn from_parts(
+        base_url: Url,
+        auth_token: String,
+        headers: Vec<(String, String)>,
+        retry_policy: RetryPolicy,
+        transport: Arc<dyn HttpTransport>,
+    ) -> Self {
         Self {
-            base_url: base_url.as_ref().parse().unwrap(),
-            auth_token: auth_token.into(),
+            base_url,
+            auth_token,
+            headers,
+            retry_policy,
+            transport,
+        }
+    }
+
+    /// Sets when failed requests are sent again. See [`RetryPolicy`](struct.RetryPolicy.html) for
+    /// the default behaviour.
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends a request, retrying according to the retry policy. Returns the first successful
+    /// response.
+    async fn send(
+        &self,
+        endpoint: &str,
+        json: Option<&serde_json::Value>,
+        idempotent: bool,
+    ) -> MispResult<HttpResponse> {
+        let endpoint_url = self.base_url.join(endpoint)?;
+        let mut headers = vec![
+            ("Authorization".to_string(), self.auth_token.clone()),
+            ("Accept".to_string(), "application/json".to_string()),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ];
+        // A configured header replaces the default one of the same name
+        headers.retain(|(name, _)| {
+            !self
+                .headers
+                .iter()
+                .any(|(configured, _)| configured.eq_ignore_ascii_case(name))
+        });
+        headers.extend(self.headers.iter().cloned());
+        let (method, body) = match json {
+            Some(json) => (Method::Post, Some(serde_json::to_vec(json)?)),
+            None => (Method::Get, None),
+        };
+        let request = HttpRequest::new(method, endpoint_url, headers, body);
+        let mut attempt = 1;
+        loop {
+            let result = match self.transport.send(request.clone()).await {
+                Ok(response) => check_status(response).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Err(e) if self.retry_policy.should_retry(&e, attempt, idempotent) => {
+                    let delay = self.retry_policy.delay(&e, attempt);
+                    log::warn!(
+                        "Request to {} failed (attempt {}), retrying in {:?}: {}",
+                        endpoint,
+                        attempt,
+                        delay,
+                        e
+                    );
+                    futures_timer::Delay::new(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
     }
 
@@ -69,41 +183,62 @@ impl MISP {
         &self,
         endpoint: impl AsRef<str>,
     ) -> MispResult<T> {
-        let endpoint_url = self.base_url.join(endpoint.as_ref())?;
-        let body_bytes = surf::get(endpoint_url)
-            .set_header(AUTHORIZATION, self.auth_token.as_str())
-            .set_header(ACCEPT, "application/json")
-            .set_header(CONTENT_TYPE, "application/json")
-            .set_header(USER_AGENT, "rs_misp")
-            .recv_bytes()
-            .await?;
-        println!("{}", String::from_utf8(body_bytes.clone()).unwrap());
+        let response = self.send(endpoint.as_ref(), None, true).await?;
+        let body_bytes = read_body(response).await?;
         Ok(serde_json::from_slice::<T>(&body_bytes)?)
     }
 
+    /// Posts to an endpoint that changes data on the server. By default, it is not retried.
     pub(crate) async fn internal_api_call_post<T: DeserializeOwned>(
         &self,
         endpoint: impl AsRef<str>,
         json: &impl Serialize,
     ) -> MispResult<T> {
-        /*
-                println!(
-                    "Sending json: {:?}",
-                    serde_json::to_string_pretty(json).unwrap()
-                );
-        */
-        let endpoint_url = self.base_url.join(endpoint.as_ref())?;
-        let body_bytes = surf::post(endpoint_url)
-            .set_header(AUTHORIZATION, self.auth_token.as_str())
-            .set_header(ACCEPT, "application/json")
-            .set_header(CONTENT_TYPE, "application/json")
-            .set_header(USER_AGENT, "rs_misp")
-            .body_json(json)?
-            .recv_bytes()
-            .await?;
+        let json = serde_json::to_value(json)?;
+        let response = self.send(endpoint.as_ref(), Some(&json), false).await?;
+        let body_bytes = read_body(response).await?;
+        Ok(serde_json::from_slice::<T>(&body_bytes)?)
+    }
+
+    /// Posts to an endpoint that only reads data, e.g. a restSearch. It is retried like a GET.
+    pub(crate) async fn internal_api_call_search<T: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &impl Serialize,
+    ) -> MispResult<T> {
+        let json = serde_json::to_value(json)?;
+        let response = self.send(endpoint.as_ref(), Some(&json), true).await?;
+        let body_bytes = read_body(response).await?;
         Ok(serde_json::from_slice::<T>(&body_bytes)?)
     }
 
+    /// Posts to a search endpoint that responds with a list, e.g. a restSearch, and deserializes
+    /// the elements of the list at `path` (see `json_stream::ArraySplitter`) while the response
+    /// is received. The body is never buffered completely. Only failures before the response
+    /// started are retried.
+    pub(crate) async fn internal_api_call_search_stream<T: DeserializeOwned + 'static>(
+        &self,
+        endpoint: impl AsRef<str>,
+        json: &impl Serialize,
+        path: &'static [&'static str],
+    ) -> MispResult<impl Stream<Item = MispResult<T>>> {
+        let json = serde_json::to_value(json)?;
+        let response = self.send(endpoint.as_ref(), Some(&json), true).await?;
+        Ok(json_stream::array_elements(response.into_body(), path))
+    }
+
+    /// Gets an endpoint that responds with a list and deserializes the elements of the list at
+    /// `path` while the response is received, like
+    /// [`internal_api_call_search_stream`](#method.internal_api_call_search_stream).
+    pub(crate) async fn internal_api_call_get_stream<T: DeserializeOwned + 'static>(
+        &self,
+        endpoint: impl AsRef<str>,
+        path: &'static [&'static str],
+    ) -> MispResult<impl Stream<Item = MispResult<T>>> {
+        let response = self.send(endpoint.as_ref(), None, true).await?;
+        Ok(json_stream::array_elements(response.into_body(), path))
+    }
+
     pub async fn server_info(&self) -> MispResult<ServerInfo> {
         Ok(self
             .internal_api_call_get("servers/getVersion.json")
@@ -113,6 +248,26 @@ impl MISP {
     pub fn events(&self) -> EventsApi<'_> {
         EventsApi::new(self)
     }
+
+    pub fn attributes(&self) -> AttributesApi<'_> {
+        AttributesApi::new(self)
+    }
+
+    pub fn objects(&self) -> ObjectsApi<'_> {
+        ObjectsApi::new(self)
+    }
+
+    pub fn tags(&self) -> TagsApi<'_> {
+        TagsApi::new(self)
+    }
+
+    pub fn galaxies(&self) -> GalaxiesApi<'_> {
+        GalaxiesApi::new(self)
+    }
+
+    pub fn proposals(&self) -> ProposalsApi<'_> {
+        ProposalsApi::new(self)
+    }
 }
 
 #[cfg(test)]
@@ -130,4 +285,39 @@ pub mod test {
         assert_eq!(misp.base_url.to_string(), "https://test.xyz/");
         assert_eq!(misp.auth_token, "12345678");
     }
+
+    #[test]
+    fn configured_headers_replace_defaults() {
+        use crate::transport::fake::{response, FakeTransport};
+        let transport = FakeTransport::new(|_| {
+            response(
+                200,
+                r#"{"version": "2.4.130", "perm_sync": false, "perm_sighting": true}"#,
+            )
+        });
+        let misp = MISP::builder("https://test.xyz", "12345678")
+            .retry_policy(RetryPolicy::never())
+            .transport(transport.clone())
+            .header("accept", "application/xml")
+            .header("User-Agent", "threat-sync/1.2")
+            .header("X-Request-Source", "tests")
+            .build()
+            .unwrap();
+        futures::executor::block_on(misp.server_info()).unwrap();
+
+        let request = &transport.requests()[0];
+        let values = |name: &str| -> Vec<&str> {
+            request
+                .headers()
+                .iter()
+                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+                .collect()
+        };
+        assert_eq!(vec!["application/xml"], values("Accept"));
+        assert_eq!(vec!["threat-sync/1.2"], values("User-Agent"));
+        assert_eq!(vec!["12345678"], values("Authorization"));
+        assert_eq!(vec!["application/json"], values("Content-Type"));
+        assert_eq!(vec!["tests"], values("X-Request-Source"));
+    }
 }
@@ -1,13 +1,151 @@
-#[cfg(feature = "serde")]
-use serde::export::Formatter;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Formatter;
+use std::time::Duration;
 
 pub type MispResult<T> = std::result::Result<T, MispError>;
 
 #[derive(Debug)]
 pub enum MispError {
     UrlParseError(url::ParseError),
-    HttpError(surf::Error),
+    /// No response was received, e.g. because the connection was refused or timed out.
+    HttpError(Box<dyn std::error::Error + Send + Sync>),
     JsonError(serde_json::error::Error),
+    IoError(std::io::Error),
+    /// The client could not be created from the settings of the
+    /// [`MISPBuilder`](struct.MISPBuilder.html), e.g. because the proxy URI is invalid.
+    ConfigurationError(String),
+    /// The requested entity does not exist or is not visible to the user (HTTP 404).
+    NotFound(ApiErrorResponse),
+    /// The user is not allowed to perform the action (HTTP 403).
+    Forbidden(ApiErrorResponse),
+    /// The authorization token is invalid or the user has no API access (HTTP 401, or 403 with
+    /// an authentication message).
+    AuthFailed(ApiErrorResponse),
+    /// The server rejected the submitted data. `field_errors` maps every invalid field (e.g.
+    /// `Event.info`) to its validation messages.
+    ValidationFailed {
+        response: ApiErrorResponse,
+        field_errors: BTreeMap<String, Vec<String>>,
+    },
+    /// Too many requests were sent (HTTP 429). `retry_after` is the delay requested by the
+    /// server, if any.
+    RateLimited {
+        response: ApiErrorResponse,
+        retry_after: Option<Duration>,
+    },
+    /// Any other status that is not successful.
+    UnexpectedStatus {
+        status: u16,
+        response: ApiErrorResponse,
+    },
+}
+
+/// The body MISP sends along with an unsuccessful status.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ApiErrorResponse {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawApiErrorResponse {
+    #[serde(flatten)]
+    response: ApiErrorResponse,
+    #[serde(default)]
+    errors: Option<Value>,
+}
+
+impl ApiErrorResponse {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The path of the endpoint that failed.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Collects the validation errors by field. Nested models are joined by a dot (e.g. `Event.info`).
+/// Errors that are not tied to a field are listed under an empty key.
+fn collect_field_errors(prefix: &str, value: &Value, errors: &mut BTreeMap<String, Vec<String>>) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                let key = match prefix {
+                    "" => field.clone(),
+                    prefix => format!("{}.{}", prefix, field),
+                };
+                collect_field_errors(&key, value, errors);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_field_errors(prefix, value, errors);
+            }
+        }
+        Value::Null => {}
+        Value::String(message) => errors
+            .entry(prefix.to_string())
+            .or_default()
+            .push(message.clone()),
+        other => errors
+            .entry(prefix.to_string())
+            .or_default()
+            .push(other.to_string()),
+    }
+}
+
+impl MispError {
+    /// Builds the error for an unsuccessful response given its status and body. Bodies that are
+    /// not MISP errors (e.g. the HTML page of a proxy) are kept as message.
+    pub(crate) fn from_response(
+        status: u16,
+        body: &[u8],
+        retry_after: Option<Duration>,
+    ) -> MispError {
+        let mut field_errors = BTreeMap::new();
+        let response = match serde_json::from_slice::<RawApiErrorResponse>(body) {
+            Ok(raw) => {
+                if let Some(errors) = &raw.errors {
+                    collect_field_errors("", errors, &mut field_errors);
+                }
+                raw.response
+            }
+            Err(_) => ApiErrorResponse {
+                message: String::from_utf8_lossy(body).trim().to_string(),
+                ..ApiErrorResponse::default()
+            },
+        };
+        match status {
+            401 => MispError::AuthFailed(response),
+            404 => MispError::NotFound(response),
+            429 => MispError::RateLimited {
+                response,
+                retry_after,
+            },
+            // MISP answers invalid submissions with a 403 that lists the invalid fields
+            _ if !field_errors.is_empty() => MispError::ValidationFailed {
+                response,
+                field_errors,
+            },
+            403 if response.name.starts_with("Authentication failed") => {
+                MispError::AuthFailed(response)
+            }
+            403 => MispError::Forbidden(response),
+            status => MispError::UnexpectedStatus { status, response },
+        }
+    }
 }
 
 impl std::fmt::Display for MispError {
@@ -22,7 +160,8 @@ impl std::error::Error for MispError {
         match self {
             JsonError(e) => Some(e),
             UrlParseError(e) => Some(e),
-            //HttpError(e) => Some(e),
+            IoError(e) => Some(e),
+            HttpError(e) => Some(e.as_ref()),
             _ => None,
         }
     }
@@ -34,9 +173,17 @@ impl From<url::ParseError> for MispError {
     }
 }
 
+#[cfg(feature = "surf-client")]
 impl From<surf::Error> for MispError {
     fn from(value: surf::Error) -> Self {
-        MispError::HttpError(value)
+        MispError::HttpError(value.into_inner().into())
+    }
+}
+
+#[cfg(feature = "reqwest-client")]
+impl From<reqwest::Error> for MispError {
+    fn from(value: reqwest::Error) -> Self {
+        MispError::HttpError(Box::new(value))
     }
 }
 
@@ -45,3 +192,72 @@ impl From<serde_json::error::Error> for MispError {
         MispError::JsonError(value)
     }
 }
+
+impl From<std::io::Error> for MispError {
+    fn from(value: std::io::Error) -> Self {
+        MispError::IoError(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MispError;
+    use std::time::Duration;
+
+    #[test]
+    fn errors_from_response() {
+        let error = MispError::from_response(
+            403,
+            br#"{"name": "Could not add Event", "message": "Could not add Event", "url": "/events/add",
+                 "errors": {"Event": {"info": ["Info cannot be empty."], "date": ["Invalid date"]}}}"#,
+            None,
+        );
+        match error {
+            MispError::ValidationFailed {
+                response,
+                field_errors,
+            } => {
+                assert_eq!("/events/add", response.url());
+                assert_eq!(vec!["Info cannot be empty."], field_errors["Event.info"]);
+                assert_eq!(2, field_errors.len());
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+
+        let error = MispError::from_response(
+            403,
+            br#"{"name": "Authentication failed. Please make sure you pass the API key of an API enabled user along in the Authorization header.", "message": "Authentication failed.", "url": "/events"}"#,
+            None,
+        );
+        assert!(matches!(error, MispError::AuthFailed(_)));
+
+        let error = MispError::from_response(
+            404,
+            br#"{"name": "Invalid event", "message": "Invalid event", "url": "/events/view/999"}"#,
+            None,
+        );
+        match error {
+            MispError::NotFound(response) => assert_eq!("Invalid event", response.message()),
+            other => panic!("Unexpected error {:?}", other),
+        }
+
+        let error =
+            MispError::from_response(429, b"Too Many Requests", Some(Duration::from_secs(30)));
+        match error {
+            MispError::RateLimited {
+                response,
+                retry_after,
+            } => {
+                assert_eq!("Too Many Requests", response.message());
+                assert_eq!(Some(Duration::from_secs(30)), retry_after);
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+
+        let error = MispError::from_response(502, b"<html>Bad Gateway</html>", None);
+        assert!(matches!(
+            error,
+            MispError::UnexpectedStatus { status: 502, .. }
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+//! Incremental deserialization of the elements of a JSON array nested inside a response, e.g. the
+//! events of `{"response": [...]}`, while the body is still being received.
+use crate::{MispError, MispResult};
+use futures::io::{AsyncRead, AsyncReadExt};
+use futures::stream::{self, Stream};
+use std::collections::VecDeque;
+
+use serde::de::{DeserializeOwned, IgnoredAny};
+
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Splits a JSON document into the raw elements of the array found at `path`, a list of object
+/// keys starting from the root (e.g. `["response", "Attribute"]`). Only the element that is
+/// currently being received is kept in memory.
+#[derive(Debug)]
+pub(crate) struct ArraySplitter {
+    path: &'static [&'static str],
+    /// The key currently being read in every open object, or None for arrays
+    keys: Vec<Option<Vec<u8>>>,
+    /// The nesting depth of the array at `path`, once it was found
+    array_depth: Option<usize>,
+    finished: bool,
+    in_string: bool,
+    escaped: bool,
+    string: Vec<u8>,
+    last_string: Option<Vec<u8>>,
+    element: Vec<u8>,
+    /// Everything outside of the array, used to report what was received instead
+    outside: Vec<u8>,
+}
+
+impl ArraySplitter {
+    pub(crate) fn new(path: &'static [&'static str]) -> Self {
+        Self {
+            path,
+            keys: Vec::new(),
+            array_depth: None,
+            finished: false,
+            in_string: false,
+            escaped: false,
+            string: Vec::new(),
+            last_string: None,
+            element: Vec::new(),
+            outside: Vec::new(),
+        }
+    }
+
+    /// Returns true once the end of the array was received.
+    pub(crate) fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns true if the array at `path` was found in the received document.
+    pub(crate) fn found(&self) -> bool {
+        self.array_depth.is_some() || self.finished
+    }
+
+    /// The received bytes that are not part of the array.
+    pub(crate) fn outside(&self) -> &[u8] {
+        &self.outside
+    }
+
+    fn at_path(&self) -> bool {
+        self.keys.len() == self.path.len()
+            && self
+                .keys
+                .iter()
+                .zip(self.path.iter())
+                .all(|(key, expected)| key.as_deref() == Some(expected.as_bytes()))
+    }
+
+    fn flush(&mut self, elements: &mut VecDeque<Vec<u8>>) {
+        if !self.element.is_empty() {
+            elements.push_back(std::mem::take(&mut self.element));
+        }
+    }
+
+    /// Feeds the next chunk of the document. Every element of the array that was completed by
+    /// this chunk is appended to `elements`.
+    pub(crate) fn push(&mut self, bytes: &[u8], elements: &mut VecDeque<Vec<u8>>) {
+        for &b in bytes {
+            if self.finished {
+                self.outside.push(b);
+                continue;
+            }
+            match self.array_depth {
+                Some(depth) => self.push_inside(depth, b, elements),
+                None => self.push_outside(b),
+            }
+        }
+    }
+
+    fn push_inside(&mut self, depth: usize, b: u8, elements: &mut VecDeque<Vec<u8>>) {
+        if self.in_string {
+            self.element.push(b);
+            if self.escaped {
+                self.escaped = false;
+            } else if b == b'\\' {
+                self.escaped = true;
+            } else if b == b'"' {
+                self.in_string = false;
+            }
+            return;
+        }
+        let nested = self.keys.len() > depth;
+        match b {
+            b'"' => {
+                self.in_string = true;
+                self.element.push(b);
+            }
+            b'{' | b'[' => {
+                self.keys.push(None);
+                self.element.push(b);
+            }
+            b'}' | b']' if nested => {
+                self.keys.pop();
+                self.element.push(b);
+            }
+            b']' => {
+                self.flush(elements);
+                self.keys.pop();
+                self.array_depth = None;
+                self.finished = true;
+            }
+            b',' if !nested => self.flush(elements),
+            b if !nested && b.is_ascii_whitespace() => {}
+            b => self.element.push(b),
+        }
+    }
+
+    fn push_outside(&mut self, b: u8) {
+        self.outside.push(b);
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+                self.string.push(b);
+            } else if b == b'\\' {
+                self.escaped = true;
+                self.string.push(b);
+            } else if b == b'"' {
+                self.in_string = false;
+                self.last_string = Some(std::mem::take(&mut self.string));
+            } else {
+                self.string.push(b);
+            }
+            return;
+        }
+        match b {
+            b'"' => self.in_string = true,
+            b':' => {
+                if let Some(key) = self.keys.last_mut() {
+                    *key = self.last_string.take();
+                }
+            }
+            b'{' => self.keys.push(None),
+            b'[' => {
+                if self.at_path() {
+                    // The opening bracket is reported as part of the array
+                    self.outside.pop();
+                    self.array_depth = Some(self.keys.len() + 1);
+                }
+                self.keys.push(None);
+            }
+            b'}' | b']' => {
+                self.keys.pop();
+            }
+            b',' => {
+                if let Some(key) = self.keys.last_mut() {
+                    *key = None;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct State<R> {
+    reader: R,
+    splitter: ArraySplitter,
+    elements: VecDeque<Vec<u8>>,
+    done: bool,
+}
+
+/// Reads a JSON document and yields the elements of the array at `path` as soon as they are
+/// received. If the document does not contain the array (e.g. because the server returned an
+/// error message), the stream yields a single JSON error.
+pub(crate) fn array_elements<R, T>(
+    reader: R,
+    path: &'static [&'static str],
+) -> impl Stream<Item = MispResult<T>>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let state = State {
+        reader,
+        splitter: ArraySplitter::new(path),
+        elements: VecDeque::new(),
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(element) = state.elements.pop_front() {
+                let item = serde_json::from_slice::<T>(&element).map_err(MispError::from);
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            if state.splitter.finished() {
+                state.done = true;
+                continue;
+            }
+            let mut chunk = [0u8; CHUNK_SIZE];
+            match state.reader.read(&mut chunk).await {
+                Ok(0) => {
+                    state.done = true;
+                    // The body ended in the middle of the array, e.g. because the connection
+                    // was closed
+                    if state.splitter.found() {
+                        let error = std::io::Error::new(
+                            std::io::ErrorKind::UnexpectedEof,
+                            "the response ended before the end of the list",
+                        );
+                        return Some((Err(MispError::from(error)), state));
+                    }
+                    if let Err(e) =
+                        serde_json::from_slice::<Vec<IgnoredAny>>(state.splitter.outside())
+                    {
+                        return Some((Err(MispError::from(e)), state));
+                    }
+                }
+                Ok(n) => state.splitter.push(&chunk[..n], &mut state.elements),
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(MispError::from(e)), state));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{array_elements, ArraySplitter};
+    use crate::{MispError, MispResult};
+    use futures::executor::block_on;
+    use futures::io::Cursor;
+    use futures::StreamExt;
+    use serde_json::{json, Value};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn split_nested_array_in_chunks() {
+        let document = r#"{"response": {"Tag": [{"x": 1}], "Attribute": [
+            {"value": "a,b]}", "Tag": [{"name": "tlp:\"white\""}]},
+            {"value": "c"}
+        ]}}"#;
+        // Every chunk size makes the splitter stop in a different state
+        for chunk_size in 1..document.len() {
+            let mut splitter = ArraySplitter::new(&["response", "Attribute"]);
+            let mut elements = VecDeque::new();
+            for chunk in document.as_bytes().chunks(chunk_size) {
+                splitter.push(chunk, &mut elements);
+            }
+            assert!(splitter.finished());
+            let values: Vec<Value> = elements
+                .iter()
+                .map(|e| serde_json::from_slice(e).unwrap())
+                .collect();
+            assert_eq!(
+                vec![
+                    json!({"value": "a,b]}", "Tag": [{"name": "tlp:\"white\""}]}),
+                    json!({"value": "c"})
+                ],
+                values
+            );
+        }
+    }
+
+    #[test]
+    fn stream_elements() {
+        let body = Cursor::new(br#"{"response": [{"id": "1"}, {"id": "2"}, []]}"#.to_vec());
+        let values: Vec<MispResult<Value>> =
+            block_on(array_elements(body, &["response"]).collect());
+        assert_eq!(3, values.len());
+        assert_eq!(json!({"id": "2"}), *values[1].as_ref().unwrap());
+
+        let body = Cursor::new(br#"{"response": []}"#.to_vec());
+        let values: Vec<MispResult<Value>> =
+            block_on(array_elements(body, &["response"]).collect());
+        assert!(values.is_empty());
+
+        // An error message instead of the expected array
+        let body = Cursor::new(br#"{"name": "Not Found", "url": "/events"}"#.to_vec());
+        let values: Vec<MispResult<Value>> =
+            block_on(array_elements(body, &["response"]).collect());
+        assert!(matches!(values[..], [Err(MispError::JsonError(_))]));
+
+        // The connection was closed in the middle of the array
+        let body = Cursor::new(br#"{"response": [{"id": "1"}, {"id": "2"#.to_vec());
+        let values: Vec<MispResult<Value>> =
+            block_on(array_elements(body, &["response"]).collect());
+        assert_eq!(2, values.len());
+        assert_eq!(json!({"id": "1"}), *values[0].as_ref().unwrap());
+        assert!(matches!(&values[1], Err(MispError::IoError(e))
+            if e.kind() == std::io::ErrorKind::UnexpectedEof));
+    }
+}
@@ -59,13 +59,21 @@
 //! On Friday there were 109 deaths in Luxembourg
 //! ```
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
 mod client;
 mod error;
+mod json_stream;
 pub mod requests;
+mod retry;
+pub mod transport;
 
+pub use builder::MISPBuilder;
 pub use client::MISP;
-pub use error::{MispError, MispResult};
+pub use error::{ApiErrorResponse, MispError, MispResult};
 pub use misp_types;
+pub use retry::RetryPolicy;
 
 #[cfg(test)]
 mod tests {}
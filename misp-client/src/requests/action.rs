@@ -0,0 +1,86 @@
+use misp_types::serialization_helpers::lenient_bool;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The reply of the server to an action (deleting, publishing, ...) that does not return an entity.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ActionResponse {
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    saved: bool,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    success: bool,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    errors: Option<Value>,
+}
+
+impl ActionResponse {
+    /// Creates a response for actions that are done through an edit, where the server returns
+    /// the edited entity instead of an action reply.
+    pub(crate) fn from_edit(accepted: bool, message: impl Into<String>, url: String) -> Self {
+        let message = message.into();
+        Self {
+            saved: accepted,
+            success: accepted,
+            name: message.clone(),
+            message,
+            url,
+            errors: None,
+        }
+    }
+
+    /// Returns true if the server accepted and executed the action.
+    pub fn accepted(&self) -> bool {
+        (self.success || self.saved) && self.errors.is_none()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A human readable message of the server describing the outcome of the action.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The errors reported by the server, if the action was rejected.
+    pub fn errors(&self) -> Option<&Value> {
+        self.errors.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ActionResponse;
+
+    #[test]
+    fn json_to_action_response() {
+        let deleted: ActionResponse = serde_json::from_str(
+            r#"{"saved": true, "success": true, "name": "Event deleted.", "message": "Event deleted.", "url": "/events/delete/12"}"#,
+        )
+        .unwrap();
+        assert!(deleted.accepted());
+        assert_eq!(deleted.message(), "Event deleted.");
+
+        let attached: ActionResponse =
+            serde_json::from_str(r#"{"saved": true, "success": "Tag(s) attached."}"#).unwrap();
+        assert!(attached.accepted());
+
+        let rejected: ActionResponse = serde_json::from_str(
+            r#"{"saved": false, "name": "Could not publish event", "errors": "Event not found"}"#,
+        )
+        .unwrap();
+        assert!(!rejected.accepted());
+    }
+}
@@ -1,7 +1,37 @@
+use crate::requests::action::ActionResponse;
+use crate::requests::attribute::AttributeRequest;
+use crate::requests::attribute_add::AttributeAddRequest;
+use crate::requests::attribute_list::AttributeListRequest;
+use crate::requests::attribute_update::AttributeUpdateRequest;
+use crate::requests::correlation::CorrelationGraphRequest;
 use crate::requests::event::EventRequest;
+use crate::requests::event_add::EventAddRequest;
 use crate::requests::event_list::EventListRequest;
-use crate::MISP;
-use misp_types::event::GenericEventIdentifier;
+use crate::requests::event_update::EventUpdateRequest;
+use crate::requests::galaxy::GalaxyRequest;
+use crate::requests::galaxy_attach::GalaxyAttachRequest;
+use crate::requests::galaxy_cluster_list::GalaxyClusterListRequest;
+use crate::requests::galaxy_list::GalaxyListRequest;
+use crate::requests::object::ObjectRequest;
+use crate::requests::object_add::ObjectAddRequest;
+use crate::requests::object_update::ObjectUpdateRequest;
+use crate::requests::proposal::ProposalRequest;
+use crate::requests::proposal_add::ProposalAddRequest;
+use crate::requests::proposal_edit::ProposalEditRequest;
+use crate::requests::proposal_list::ProposalListRequest;
+use crate::requests::tag_add::TagAddRequest;
+use crate::requests::tag_attach::{TagAttachRequest, TagAttachment};
+use crate::requests::tag_list::TagListRequest;
+use crate::{MispResult, MISP};
+use misp_types::attribute::{AttributeUpdate, GenericAttributeIdentifier, NewAttribute};
+use misp_types::event::{EventUpdate, GenericEventIdentifier, NewEvent};
+use misp_types::galaxy::{
+    GalaxyCluster, GalaxyClusterEmbedded, GalaxyClusterIdentifier, GenericGalaxyIdentifier,
+};
+use misp_types::object::{GenericObjectIdentifier, NewObject, ObjectUpdate};
+use misp_types::shadow_attribute::ShadowAttributeIdentifier;
+use misp_types::tag::{GenericTagIdentifier, NewTag};
+use uuid::Uuid;
 
 pub struct EventsApi<'a> {
     misp_client: &'a MISP,
@@ -13,7 +43,7 @@ impl<'a> EventsApi<'a> {
         EventsApi { misp_client }
     }
 
-    pub fn list(&self) -> EventListRequest<'_> {
+    pub fn list(&self) -> EventListRequest<'a> {
         EventListRequest::new(self.misp_client, None)
     }
 
@@ -21,7 +51,476 @@ impl<'a> EventsApi<'a> {
         EventRequest::new(self.misp_client, event.into())
     }
 
-    pub fn add() {}
+    /// Creates a new event, including its attributes, objects and tags, on the server.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use misp_client::misp_types::attribute::NewAttribute;
+    /// # use misp_client::misp_types::attribute_type::AttributeType;
+    /// # use misp_client::misp_types::event::NewEvent;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let mut event = NewEvent::new("Phishing campaign");
+    /// event
+    ///     .add_tag("tlp:amber")
+    ///     .add_attribute(NewAttribute::new(AttributeType::Domain, "evil.example"));
+    ///
+    /// let created = misp.events().add(event).send().await?;
+    /// println!("Created event {}", created.uuid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&self, event: NewEvent) -> EventAddRequest<'a> {
+        EventAddRequest::new(self.misp_client, event)
+    }
+
+    /// Edits an existing event. Only the fields that are set in `update` are changed on the
+    /// server.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use misp_client::misp_types::analysis::Analysis;
+    /// # use misp_client::misp_types::event::EventUpdate;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let mut update = EventUpdate::new();
+    /// update.info("Phishing campaign (confirmed)").analysis(Analysis::Complete);
+    ///
+    /// let event = misp.events().update(1188, update).send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+        update: EventUpdate,
+    ) -> EventUpdateRequest<'a> {
+        EventUpdateRequest::new(self.misp_client, event.into(), update)
+    }
+
+    /// Builds a graph of the events correlating with an event, following the correlations up to
+    /// a configurable depth.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let graph = misp
+    ///     .events()
+    ///     .correlation_graph(1188)
+    ///     .depth(2)
+    ///     .limit(50)
+    ///     .build()
+    ///     .await?;
+    /// for (a, b) in graph.edges() {
+    ///     println!("{} <-> {}", a, b);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn correlation_graph(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+    ) -> CorrelationGraphRequest<'a> {
+        CorrelationGraphRequest::new(self.misp_client, event.into())
+    }
+}
+
+pub struct AttributesApi<'a> {
+    misp_client: &'a MISP,
+}
+
+/// AttributesApi is bound to the lifetime of the MISP client instance
+impl<'a> AttributesApi<'a> {
+    pub fn new(misp_client: &'a MISP) -> AttributesApi<'a> {
+        AttributesApi { misp_client }
+    }
+
+    /// Searches attributes across all events (`attributes/restSearch`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use misp_client::misp_types::attribute_type::AttributeType;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let ips = misp
+    ///     .attributes()
+    ///     .list()
+    ///     .of_kind(AttributeType::IpDst)
+    ///     .to_ids(true)
+    ///     .retrieve()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list(&self) -> AttributeListRequest<'a> {
+        AttributeListRequest::new(self.misp_client)
+    }
+
+    pub fn get(&self, attribute: impl Into<GenericAttributeIdentifier>) -> AttributeRequest<'a> {
+        AttributeRequest::new(self.misp_client, attribute.into())
+    }
+
+    /// Adds a new attribute to an existing event.
+    pub fn add(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+        attribute: NewAttribute,
+    ) -> AttributeAddRequest<'a> {
+        AttributeAddRequest::new(self.misp_client, event.into(), attribute)
+    }
+
+    /// Edits an existing attribute. Only the fields that are set in `update` are changed on the
+    /// server.
+    pub fn update(
+        &self,
+        attribute: impl Into<GenericAttributeIdentifier>,
+        update: AttributeUpdate,
+    ) -> AttributeUpdateRequest<'a> {
+        AttributeUpdateRequest::new(self.misp_client, attribute.into(), update)
+    }
+}
+
+pub struct ObjectsApi<'a> {
+    misp_client: &'a MISP,
+}
+
+/// ObjectsApi is bound to the lifetime of the MISP client instance
+impl<'a> ObjectsApi<'a> {
+    pub fn new(misp_client: &'a MISP) -> ObjectsApi<'a> {
+        ObjectsApi { misp_client }
+    }
+
+    pub fn get(&self, object: impl Into<GenericObjectIdentifier>) -> ObjectRequest<'a> {
+        ObjectRequest::new(self.misp_client, object.into())
+    }
+
+    /// Adds a new object, including its attributes, to an existing event.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use misp_client::misp_types::object::NewObject;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let mut object = NewObject::domain_ip("evil.example");
+    /// object.add_value("ip", "198.51.100.7").unwrap();
+    ///
+    /// let created = misp.objects().add(1188, object).send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+        object: NewObject,
+    ) -> ObjectAddRequest<'a> {
+        ObjectAddRequest::new(self.misp_client, event.into(), object)
+    }
+
+    /// Edits an existing object. Only the fields that are set in `update` are changed on the
+    /// server.
+    pub fn update(
+        &self,
+        object: impl Into<GenericObjectIdentifier>,
+        update: ObjectUpdate,
+    ) -> ObjectUpdateRequest<'a> {
+        ObjectUpdateRequest::new(self.misp_client, object.into(), update)
+    }
+
+    /// Soft deletes an object reference.
+    pub async fn delete_reference(&self, reference: Uuid) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post(
+                format!("objectReferences/delete/{}", reference),
+                &serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Deletes an object reference permanently.
+    pub async fn hard_delete_reference(&self, reference: Uuid) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post(
+                format!("objectReferences/delete/{}/1", reference),
+                &serde_json::json!({}),
+            )
+            .await
+    }
+}
+
+pub struct TagsApi<'a> {
+    misp_client: &'a MISP,
+}
+
+/// TagsApi is bound to the lifetime of the MISP client instance
+impl<'a> TagsApi<'a> {
+    pub fn new(misp_client: &'a MISP) -> TagsApi<'a> {
+        TagsApi { misp_client }
+    }
+
+    /// Lists all tags known to the server.
+    pub fn list(&self) -> TagListRequest<'a> {
+        TagListRequest::new(self.misp_client, None)
+    }
 
-    pub fn update() {}
+    /// Searches tags by name. The name is compared case insensitively and `%` can be used as
+    /// wildcard (e.g. `tlp:%`).
+    pub fn search(&self, name: impl Into<String>) -> TagListRequest<'a> {
+        TagListRequest::new(self.misp_client, Some(name.into()))
+    }
+
+    /// Creates a new tag on the server.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use misp_client::misp_types::tag::NewTag;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let mut tag = NewTag::new("campaign:operation-x");
+    /// tag.colour("#ff0000");
+    ///
+    /// let created = misp.tags().add(tag).send().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&self, tag: NewTag) -> TagAddRequest<'a> {
+        TagAddRequest::new(self.misp_client, tag)
+    }
+
+    /// Attaches an existing tag, given by its id or its name, to the event or attribute with the
+    /// given uuid. A name that only consists of digits is taken as an id by the server.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use uuid::Uuid;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// # let event_uuid = Uuid::nil();
+    /// let response = misp
+    ///     .tags()
+    ///     .attach(event_uuid, "tlp:amber")
+    ///     .local()
+    ///     .send()
+    ///     .await?;
+    /// assert!(response.accepted());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn attach(
+        &self,
+        target: Uuid,
+        tag: impl Into<GenericTagIdentifier>,
+    ) -> TagAttachRequest<'a> {
+        TagAttachRequest::new(self.misp_client, target, tag.into())
+    }
+
+    /// Removes a tag, local or not, from the event or attribute with the given uuid.
+    pub async fn detach(
+        &self,
+        target: Uuid,
+        tag: impl Into<GenericTagIdentifier>,
+    ) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post(
+                "tags/removeTagFromObject",
+                &TagAttachment::new(target, tag.into()),
+            )
+            .await
+    }
+}
+
+pub struct GalaxiesApi<'a> {
+    misp_client: &'a MISP,
+}
+
+/// GalaxiesApi is bound to the lifetime of the MISP client instance
+impl<'a> GalaxiesApi<'a> {
+    pub fn new(misp_client: &'a MISP) -> GalaxiesApi<'a> {
+        GalaxiesApi { misp_client }
+    }
+
+    /// Lists all galaxies known to the server, without their clusters.
+    pub fn list(&self) -> GalaxyListRequest<'a> {
+        GalaxyListRequest::new(self.misp_client)
+    }
+
+    /// Gets a single galaxy. Fetching the galaxy through the request includes all of its
+    /// clusters.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let clusters = misp.galaxies().get(3).search_clusters("Fancy Bear").await?;
+    /// for cluster in clusters {
+    ///     println!("{}: {:?}", cluster.value(), cluster.synonyms());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, galaxy: impl Into<GenericGalaxyIdentifier>) -> GalaxyRequest<'a> {
+        GalaxyRequest::new(self.misp_client, galaxy.into())
+    }
+
+    /// Searches the clusters of all galaxies, e.g. by value or synonym.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let clusters = misp.galaxies().search_clusters().with_synonym("APT28").retrieve().await?;
+    /// for cluster in clusters {
+    ///     println!("{}: {}", cluster.value(), cluster.tag_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_clusters(&self) -> GalaxyClusterListRequest<'a> {
+        GalaxyClusterListRequest::new(self.misp_client)
+    }
+
+    /// Attaches a galaxy cluster to an event.
+    pub fn attach_to_event(
+        &self,
+        cluster: impl Into<GalaxyClusterIdentifier>,
+        event: impl Into<GenericEventIdentifier>,
+    ) -> GalaxyAttachRequest<'a> {
+        GalaxyAttachRequest::new(
+            self.misp_client,
+            cluster.into(),
+            "event",
+            event.into().to_url_id(),
+        )
+    }
+
+    /// Attaches a galaxy cluster to an attribute.
+    pub fn attach_to_attribute(
+        &self,
+        cluster: impl Into<GalaxyClusterIdentifier>,
+        attribute: impl Into<GenericAttributeIdentifier>,
+    ) -> GalaxyAttachRequest<'a> {
+        GalaxyAttachRequest::new(
+            self.misp_client,
+            cluster.into(),
+            "attribute",
+            attribute.into().to_url_id(),
+        )
+    }
+
+    /// Removes a galaxy cluster from the event or attribute with the given uuid. Clusters are
+    /// attached through their tag, so this removes the tag of the cluster.
+    pub async fn detach(
+        &self,
+        cluster: &GalaxyCluster,
+        target: Uuid,
+    ) -> MispResult<ActionResponse> {
+        TagsApi::new(self.misp_client)
+            .detach(target, cluster.tag_name())
+            .await
+    }
+
+    /// Imports galaxy clusters, e.g. the custom clusters exported from another server. Clusters
+    /// that already exist (same uuid) are updated if the imported version is newer.
+    pub async fn import(&self, clusters: Vec<GalaxyCluster>) -> MispResult<ActionResponse> {
+        let clusters: Vec<GalaxyClusterEmbedded> = clusters
+            .into_iter()
+            .map(|cluster| GalaxyClusterEmbedded { cluster })
+            .collect();
+        self.misp_client
+            .internal_api_call_post("galaxies/import", &clusters)
+            .await
+    }
+}
+
+pub struct ProposalsApi<'a> {
+    misp_client: &'a MISP,
+}
+
+/// ProposalsApi is bound to the lifetime of the MISP client instance
+impl<'a> ProposalsApi<'a> {
+    pub fn new(misp_client: &'a MISP) -> ProposalsApi<'a> {
+        ProposalsApi { misp_client }
+    }
+
+    /// Lists the pending proposals.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let proposals = misp.proposals().list().in_event(1188).retrieve().await?;
+    /// for proposal in proposals.iter().filter(|p| p.is_new_attribute()) {
+    ///     misp.proposals().get(proposal).accept().await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list(&self) -> ProposalListRequest<'a> {
+        ProposalListRequest::new(self.misp_client)
+    }
+
+    pub fn get(&self, proposal: impl Into<ShadowAttributeIdentifier>) -> ProposalRequest<'a> {
+        ProposalRequest::new(self.misp_client, proposal.into())
+    }
+
+    /// Proposes a new attribute for an event owned by another organization.
+    pub fn propose_attribute(
+        &self,
+        event: impl Into<GenericEventIdentifier>,
+        attribute: NewAttribute,
+    ) -> ProposalAddRequest<'a> {
+        ProposalAddRequest::new(self.misp_client, event.into(), attribute)
+    }
+
+    /// Proposes a change to an existing attribute owned by another organization.
+    pub fn propose_edit(
+        &self,
+        attribute: impl Into<GenericAttributeIdentifier>,
+        update: AttributeUpdate,
+    ) -> ProposalEditRequest<'a> {
+        ProposalEditRequest::new(self.misp_client, attribute.into(), update)
+    }
+
+    /// Proposes to delete an existing attribute owned by another organization.
+    pub async fn propose_deletion(
+        &self,
+        attribute: impl Into<GenericAttributeIdentifier>,
+    ) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post(
+                format!("shadow_attributes/delete/{}", attribute.into().to_url_id()),
+                &serde_json::json!({}),
+            )
+            .await
+    }
 }
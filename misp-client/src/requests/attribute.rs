@@ -0,0 +1,199 @@
+use crate::requests::action::ActionResponse;
+use crate::requests::attribute_update::AttributeUpdateRequest;
+use crate::{MispResult, MISP};
+use misp_types::attribute::{
+    AttributeFull, AttributeFullEmbedded, AttributeIdentifier, AttributeUpdate,
+    GenericAttributeIdentifier,
+};
+use uuid::Uuid;
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct AttributeRequest<'a> {
+    id: GenericAttributeIdentifier,
+    misp_client: &'a MISP,
+    cached_local: Option<AttributeFull>,
+}
+
+impl AttributeRequest<'_> {
+    pub fn new(misp_client: &MISP, id: GenericAttributeIdentifier) -> AttributeRequest<'_> {
+        AttributeRequest {
+            id,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<AttributeFull> {
+        let attribute: AttributeFullEmbedded = self
+            .misp_client
+            .internal_api_call_get(format!("attributes/view/{}", self.id.to_url_id()))
+            .await?;
+        Ok(attribute.attribute)
+    }
+
+    async fn cached(&mut self) -> MispResult<&AttributeFull> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<AttributeFull> {
+        let attribute_ref = self.cached().await?;
+        Ok(attribute_ref.clone())
+    }
+
+    pub async fn id(&mut self) -> MispResult<AttributeIdentifier> {
+        match self.id {
+            GenericAttributeIdentifier::Global(_) => Ok(self.cached().await?.id()),
+            GenericAttributeIdentifier::Local(id) => Ok(id),
+        }
+    }
+
+    pub async fn uuid(&mut self) -> MispResult<Uuid> {
+        match self.id {
+            GenericAttributeIdentifier::Global(uuid) => Ok(uuid),
+            GenericAttributeIdentifier::Local(_) => Ok(self.cached().await?.uuid()),
+        }
+    }
+
+    /// Edits the attribute. Only the fields that are set in `update` are changed on the server.
+    pub async fn edit(&mut self, update: AttributeUpdate) -> MispResult<AttributeFull> {
+        let attribute = AttributeUpdateRequest::new(self.misp_client, self.id.clone(), update)
+            .send()
+            .await?;
+        self.cached_local = Some(attribute.clone());
+        Ok(attribute)
+    }
+
+    async fn delete_internal(&mut self, hard: bool) -> MispResult<ActionResponse> {
+        let endpoint = if hard {
+            format!("attributes/delete/{}/1", self.id.to_url_id())
+        } else {
+            format!("attributes/delete/{}", self.id.to_url_id())
+        };
+        let response = self
+            .misp_client
+            .internal_api_call_post(endpoint, &serde_json::json!({}))
+            .await?;
+        self.cached_local = None;
+        Ok(response)
+    }
+
+    /// Soft deletes the attribute: it is flagged as deleted, but stays on the server and can be
+    /// restored with [`restore`](#method.restore).
+    pub async fn delete(&mut self) -> MispResult<ActionResponse> {
+        self.delete_internal(false).await
+    }
+
+    /// Deletes the attribute permanently from the server.
+    pub async fn hard_delete(&mut self) -> MispResult<ActionResponse> {
+        self.delete_internal(true).await
+    }
+
+    /// Restores a soft deleted attribute.
+    pub async fn restore(&mut self) -> MispResult<AttributeFull> {
+        let attribute: AttributeFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("attributes/restore/{}", self.id.to_url_id()),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.cached_local = Some(attribute.attribute.clone());
+        Ok(attribute.attribute)
+    }
+
+    async fn edit_action(
+        &mut self,
+        update: AttributeUpdate,
+        accepted: impl FnOnce(&AttributeFull) -> bool,
+        message: &str,
+    ) -> MispResult<ActionResponse> {
+        let attribute = self.edit(update).await?;
+        Ok(ActionResponse::from_edit(
+            accepted(&attribute),
+            message,
+            format!("/attributes/edit/{}", self.id.to_url_id()),
+        ))
+    }
+
+    /// Sets or removes the IDS flag, which marks the attribute as usable for automatic detection.
+    pub async fn set_to_ids(&mut self, to_ids: bool) -> MispResult<ActionResponse> {
+        let mut update = AttributeUpdate::new();
+        update.to_ids(to_ids);
+        let message = if to_ids {
+            "IDS flag set."
+        } else {
+            "IDS flag removed."
+        };
+        self.edit_action(update, |a| a.to_ids() == to_ids, message)
+            .await
+    }
+
+    /// Stops correlating this attribute with the attributes of other events.
+    pub async fn disable_correlation(&mut self) -> MispResult<ActionResponse> {
+        self.set_correlation(false).await
+    }
+
+    pub async fn enable_correlation(&mut self) -> MispResult<ActionResponse> {
+        self.set_correlation(true).await
+    }
+
+    async fn set_correlation(&mut self, enabled: bool) -> MispResult<ActionResponse> {
+        let mut update = AttributeUpdate::new();
+        update.disable_correlation(!enabled);
+        let message = if enabled {
+            "Correlation enabled."
+        } else {
+            "Correlation disabled."
+        };
+        self.edit_action(update, |a| a.disable_correlation() != enabled, message)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transport::fake::{response, FakeTransport};
+    use futures::executor::block_on;
+
+    #[test]
+    fn toggles_send_partial_edits() {
+        let transport = FakeTransport::new(|_| {
+            response(
+                200,
+                r#"{"Attribute": {
+                    "id": "11", "event_id": "7", "object_id": "0", "object_relation": null,
+                    "category": "Network activity", "type": "ip-dst", "value": "198.51.100.7",
+                    "to_ids": false, "uuid": "00000000-0000-4000-8000-000000000011",
+                    "timestamp": "1590000000", "distribution": "5", "sharing_group_id": "0",
+                    "comment": "", "deleted": false, "disable_correlation": true,
+                    "first_seen": null, "last_seen": null
+                }}"#,
+            )
+        });
+        let misp = transport.client();
+        let mut attribute = misp.attributes().get(11);
+        assert!(block_on(attribute.set_to_ids(false)).unwrap().accepted());
+        assert!(!block_on(attribute.set_to_ids(true)).unwrap().accepted());
+        assert!(block_on(attribute.disable_correlation())
+            .unwrap()
+            .accepted());
+        assert!(!block_on(attribute.enable_correlation()).unwrap().accepted());
+
+        assert!(transport
+            .requests()
+            .iter()
+            .all(|r| r.url().path() == "/attributes/edit/11"));
+        assert_eq!(
+            vec![
+                serde_json::json!({"Attribute": {"to_ids": false}}),
+                serde_json::json!({"Attribute": {"to_ids": true}}),
+                serde_json::json!({"Attribute": {"disable_correlation": true}}),
+                serde_json::json!({"Attribute": {"disable_correlation": false}}),
+            ],
+            transport.bodies()
+        );
+    }
+}
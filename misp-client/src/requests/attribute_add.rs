@@ -0,0 +1,39 @@
+use crate::{MispResult, MISP};
+use misp_types::attribute::{AttributeFull, AttributeFullEmbedded, NewAttribute};
+use misp_types::event::GenericEventIdentifier;
+
+/// Adds a new attribute to an existing event.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct AttributeAddRequest<'a> {
+    event: GenericEventIdentifier,
+    attribute: NewAttribute,
+    misp_client: &'a MISP,
+}
+
+impl AttributeAddRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        event: GenericEventIdentifier,
+        attribute: NewAttribute,
+    ) -> AttributeAddRequest<'_> {
+        AttributeAddRequest {
+            event,
+            attribute,
+            misp_client,
+        }
+    }
+
+    /// Sends the attribute to the server. Returns the created attribute, including the id and uuid
+    /// assigned by the server.
+    pub async fn send(&self) -> MispResult<AttributeFull> {
+        let attribute: AttributeFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("attributes/add/{}", self.event.to_url_id()),
+                &self.attribute,
+            )
+            .await?;
+        Ok(attribute.attribute)
+    }
+}
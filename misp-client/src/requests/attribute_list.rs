@@ -0,0 +1,325 @@
+use crate::requests::tag_query::TagQuery;
+use crate::{MispResult, MISP};
+use chrono::{Date, Utc};
+use futures::{Stream, TryFutureExt, TryStreamExt};
+use misp_types::attribute::AttributeFull;
+use misp_types::attribute_type::{AttributeCategory, AttributeType};
+use misp_types::event::EventIdentifier;
+use misp_types::organization::GenericOrganizationIdentifier;
+
+use misp_types::serialization_helpers::option_date_to_mispdate;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AttributeSearchQuery {
+    #[serde(rename = "returnFormat")]
+    return_format: String,
+
+    #[serde(rename = "org")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    organization: Option<GenericOrganizationIdentifier>,
+
+    #[serde(rename = "eventid")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<EventIdentifier>,
+
+    #[serde(rename = "from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "option_date_to_mispdate")]
+    after: Option<Date<Utc>>,
+
+    #[serde(rename = "to")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "option_date_to_mispdate")]
+    before: Option<Date<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<AttributeType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<AttributeCategory>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<TagQuery>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_ids: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EmbeddedAttributeSearchQuery {
+    request: AttributeSearchQuery,
+}
+
+impl AttributeSearchQuery {
+    pub fn new() -> Self {
+        Self {
+            return_format: "json".into(),
+            organization: None,
+            event: None,
+            after: None,
+            before: None,
+            value: None,
+            kind: None,
+            category: None,
+            tags: None,
+            to_ids: None,
+            deleted: None,
+            limit: None,
+        }
+    }
+}
+
+impl Default for AttributeSearchQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The Request's lifetime is bound to the client's lifetime
+pub struct AttributeListRequest<'a> {
+    search_query: EmbeddedAttributeSearchQuery,
+    misp_client: &'a MISP,
+    cached_local: Option<Vec<AttributeFull>>,
+}
+
+impl AttributeListRequest<'_> {
+    pub fn new(misp_client: &MISP) -> AttributeListRequest<'_> {
+        AttributeListRequest {
+            search_query: EmbeddedAttributeSearchQuery {
+                request: AttributeSearchQuery::new(),
+            },
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Vec<AttributeFull>> {
+        self.stream().try_collect().await
+    }
+
+    async fn cached(&mut self) -> MispResult<&Vec<AttributeFull>> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    /// Downloads all the attributes matching the set filters
+    pub async fn retrieve(&mut self) -> MispResult<Vec<AttributeFull>> {
+        Ok(self.cached().await?.clone())
+    }
+
+    /// Filters the attributes based on the organization that is currently owning their event.
+    pub fn from_organization(
+        &mut self,
+        organization: impl Into<GenericOrganizationIdentifier>,
+    ) -> &mut Self {
+        self.search_query.request.organization = Some(organization.into());
+        self
+    }
+
+    /// Only returns the attributes of a specific event.
+    pub fn in_event(&mut self, event: EventIdentifier) -> &mut Self {
+        self.search_query.request.event = Some(event);
+        self
+    }
+
+    /// Filters attributes that contain a specific text inside their value.
+    ///
+    /// If you only want to find attributes with the exact same value, use
+    /// [`with_value`](#method.with_value).
+    pub fn containing_value(&mut self, search: impl AsRef<str>) -> &mut Self {
+        self.search_query.request.value = Some(format!("%{}%", search.as_ref()));
+        self
+    }
+
+    /// Filters attributes that have a specific value.
+    pub fn with_value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.search_query.request.value = Some(value.into());
+        self
+    }
+
+    /// Filters attributes of a specific MISP type (e.g. `ip-dst`).
+    pub fn of_kind(&mut self, kind: AttributeType) -> &mut Self {
+        self.search_query.request.kind = Some(kind);
+        self
+    }
+
+    pub fn in_category(&mut self, category: AttributeCategory) -> &mut Self {
+        self.search_query.request.category = Some(category);
+        self
+    }
+
+    /// Filters attributes by their tags. When called several times, the conditions of all the
+    /// queries are combined.
+    pub fn matching_tags(&mut self, query: TagQuery) -> &mut Self {
+        self.search_query
+            .request
+            .tags
+            .get_or_insert_with(TagQuery::default)
+            .merge(query);
+        self
+    }
+
+    /// Filters attributes that have a specific tag. When called several times, the attributes
+    /// need to have all of the tags.
+    pub fn with_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.matching_tags(TagQuery::all_of(Some(name)))
+    }
+
+    /// Filters attributes that have at least one of the given tags.
+    pub fn with_any_tag(
+        &mut self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> &mut Self {
+        self.matching_tags(TagQuery::any_of(names))
+    }
+
+    /// Excludes the attributes that have a specific tag.
+    pub fn without_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.matching_tags(TagQuery::none_of(Some(name)))
+    }
+
+    /// Filters attributes based on their IDS flag.
+    pub fn to_ids(&mut self, to_ids: bool) -> &mut Self {
+        self.search_query.request.to_ids = Some(to_ids);
+        self
+    }
+
+    /// Also returns soft deleted attributes.
+    pub fn include_deleted(&mut self) -> &mut Self {
+        self.search_query.request.deleted = Some(true);
+        self
+    }
+
+    /// Filters attributes of events that happened after a specific date.
+    pub fn after(&mut self, date: Date<Utc>) -> &mut Self {
+        self.search_query.request.after = Some(date);
+        self
+    }
+
+    /// Filters attributes of events that happened before a specific date.
+    pub fn before(&mut self, date: Date<Utc>) -> &mut Self {
+        self.search_query.request.before = Some(date);
+        self
+    }
+
+    /// Limits the amount of results
+    pub fn limit(&mut self, limit: u64) -> &mut Self {
+        self.search_query.request.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a> AttributeListRequest<'a> {
+    /// Downloads the attributes matching the set filters and yields them one at a time, in the
+    /// order of the server. Unlike [`retrieve`](#method.retrieve), the attributes are not cached
+    /// and every attribute is deserialized as soon as it is received.
+    pub fn stream(&self) -> impl Stream<Item = MispResult<AttributeFull>> + Unpin + 'a {
+        let misp_client = self.misp_client;
+        let query = self.search_query.clone();
+        let attributes = async move {
+            misp_client
+                .internal_api_call_search_stream(
+                    "attributes/restSearch",
+                    &query,
+                    &["response", "Attribute"],
+                )
+                .await
+        }
+        .try_flatten_stream();
+        Box::pin(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AttributeListRequest, AttributeSearchQuery};
+    use crate::transport::fake::{chunked_response, FakeTransport};
+    use crate::MISP;
+    use chrono::{TimeZone, Utc};
+    use futures::executor::{block_on, block_on_stream};
+    use misp_types::attribute_type::{AttributeCategory, AttributeType};
+    use misp_types::event::EventIdentifier;
+
+    #[test]
+    fn search_query_to_json() {
+        let misp = MISP::new("https://test.xyz/", "12345678");
+        let mut request = AttributeListRequest::new(&misp);
+        request
+            .in_event(EventIdentifier(7))
+            .of_kind(AttributeType::IpDst)
+            .in_category(AttributeCategory::NetworkActivity)
+            .containing_value("198.51.100")
+            .with_any_tag(vec!["tlp:white", "tlp:green"])
+            .without_tag("false-positive")
+            .to_ids(true)
+            .after(Utc.ymd(2020, 8, 1))
+            .limit(50);
+        assert_eq!(
+            serde_json::json!({"request": {
+                "returnFormat": "json",
+                "eventid": "7",
+                "type": "ip-dst",
+                "category": "Network activity",
+                "value": "%198.51.100%",
+                "tags": {"OR": ["tlp:white", "tlp:green"], "NOT": ["false-positive"]},
+                "to_ids": true,
+                "from": "2020-08-01",
+                "limit": 50
+            }}),
+            serde_json::to_value(&request.search_query).unwrap()
+        );
+        assert_eq!(
+            serde_json::json!({"returnFormat": "json"}),
+            serde_json::to_value(AttributeSearchQuery::new()).unwrap()
+        );
+    }
+
+    #[test]
+    fn unwrap_search_response() {
+        let transport = FakeTransport::new(|_| {
+            chunked_response(
+                r#"{"response": {"Attribute": [{
+                    "id": "11", "event_id": "7", "object_id": "0", "object_relation": null,
+                    "category": "Network activity", "type": "ip-dst", "value": "198.51.100.7",
+                    "to_ids": true, "uuid": "00000000-0000-4000-8000-000000000011",
+                    "timestamp": "1590000000", "distribution": "5", "sharing_group_id": "0",
+                    "comment": "", "deleted": false, "disable_correlation": false,
+                    "first_seen": null, "last_seen": null, "Tag": [{"id": "9", "name": "tlp:white"}]
+                }]}}"#,
+                5,
+            )
+        });
+        let misp = transport.client();
+        let attributes = block_on(
+            misp.attributes()
+                .list()
+                .with_value("198.51.100.7")
+                .retrieve(),
+        )
+        .unwrap();
+        assert_eq!(1, attributes.len());
+        assert_eq!("198.51.100.7", attributes[0].value());
+        let requests = transport.requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("/attributes/restSearch", requests[0].url().path());
+
+        let request = misp.attributes().list();
+        let values: Vec<String> = block_on_stream(request.stream())
+            .map(|a| a.unwrap().value().to_string())
+            .collect();
+        assert_eq!(vec!["198.51.100.7"], values);
+    }
+}
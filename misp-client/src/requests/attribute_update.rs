@@ -0,0 +1,42 @@
+use crate::{MispResult, MISP};
+use misp_types::attribute::{
+    AttributeFull, AttributeFullEmbedded, AttributeUpdate, AttributeUpdateEmbedded,
+    GenericAttributeIdentifier,
+};
+
+/// Edits an existing attribute on the server. Only the fields set in the
+/// [`AttributeUpdate`](../../misp_types/attribute/struct.AttributeUpdate.html) are sent.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct AttributeUpdateRequest<'a> {
+    id: GenericAttributeIdentifier,
+    update: AttributeUpdateEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl AttributeUpdateRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        id: GenericAttributeIdentifier,
+        update: AttributeUpdate,
+    ) -> AttributeUpdateRequest<'_> {
+        AttributeUpdateRequest {
+            id,
+            update: AttributeUpdateEmbedded { attribute: update },
+            misp_client,
+        }
+    }
+
+    /// Sends the changes to the server. Returns the attribute as it is stored on the server after
+    /// the update.
+    pub async fn send(&self) -> MispResult<AttributeFull> {
+        let attribute: AttributeFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("attributes/edit/{}", self.id.to_url_id()),
+                &self.update,
+            )
+            .await?;
+        Ok(attribute.attribute)
+    }
+}
@@ -0,0 +1,291 @@
+use crate::requests::event::EventRequest;
+use crate::{MispError, MispResult, MISP};
+use misp_types::event::{EventFull, EventIdentifier, GenericEventIdentifier};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// An in-memory graph of events connected by their correlations.
+///
+/// Every event of the graph was downloaded in full. Two events are connected when the server
+/// reports them as related, i.e. when they share at least one attribute value.
+#[derive(Debug, Clone)]
+pub struct CorrelationGraph {
+    root: EventIdentifier,
+    events: HashMap<EventIdentifier, EventFull>,
+    distances: HashMap<EventIdentifier, usize>,
+    edges: HashMap<EventIdentifier, HashSet<EventIdentifier>>,
+    unreachable: HashSet<EventIdentifier>,
+}
+
+impl CorrelationGraph {
+    fn new(root: EventIdentifier) -> Self {
+        Self {
+            root,
+            events: HashMap::new(),
+            distances: HashMap::new(),
+            edges: HashMap::new(),
+            unreachable: HashSet::new(),
+        }
+    }
+
+    /// Adds an event that is `distance` hops away from the root. Its correlations with the events
+    /// already in the graph become edges.
+    fn insert(&mut self, distance: usize, event: EventFull) {
+        let id = event.id();
+        for related in event.related_events() {
+            if self.events.contains_key(&related.id()) {
+                self.connect(id, related.id());
+            }
+        }
+        // Correlations are not always reported from both sides (e.g. when one event
+        // disabled correlation), so edges towards the new event are also looked up.
+        let incoming: Vec<EventIdentifier> = self
+            .events
+            .values()
+            .filter(|e| e.related_events().iter().any(|r| r.id() == id))
+            .map(|e| e.id())
+            .collect();
+        for other in incoming {
+            self.connect(id, other);
+        }
+        self.distances.insert(id, distance);
+        self.events.insert(id, event);
+    }
+
+    fn connect(&mut self, a: EventIdentifier, b: EventIdentifier) {
+        self.edges.entry(a).or_default().insert(b);
+        self.edges.entry(b).or_default().insert(a);
+    }
+
+    /// Returns the event the graph was built from.
+    pub fn root(&self) -> &EventFull {
+        &self.events[&self.root]
+    }
+
+    /// Returns all the events of the graph, in no particular order.
+    pub fn events(&self) -> impl Iterator<Item = &EventFull> {
+        self.events.values()
+    }
+
+    pub fn event(&self, id: EventIdentifier) -> Option<&EventFull> {
+        self.events.get(&id)
+    }
+
+    pub fn contains(&self, id: EventIdentifier) -> bool {
+        self.events.contains_key(&id)
+    }
+
+    /// The amount of events in the graph.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the amount of correlation hops between the root and the given event.
+    pub fn distance(&self, id: EventIdentifier) -> Option<usize> {
+        self.distances.get(&id).copied()
+    }
+
+    /// Returns the events of the graph that correlate with the given event.
+    pub fn neighbours(&self, id: EventIdentifier) -> Vec<&EventFull> {
+        self.edges
+            .get(&id)
+            .map(|ids| ids.iter().filter_map(|id| self.events.get(id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns every correlation of the graph once, as pair of event identifiers.
+    pub fn edges(&self) -> Vec<(EventIdentifier, EventIdentifier)> {
+        let mut edges: Vec<(EventIdentifier, EventIdentifier)> = self
+            .edges
+            .iter()
+            .flat_map(|(a, others)| others.iter().map(move |b| (*a, *b)))
+            .filter(|(a, b)| a.0 < b.0)
+            .collect();
+        edges.sort_by_key(|(a, b)| (a.0, b.0));
+        edges
+    }
+
+    /// Returns the related events that could not be downloaded, because they were deleted or are
+    /// not visible to the user. They are not part of the graph.
+    pub fn unreachable(&self) -> Vec<EventIdentifier> {
+        let mut unreachable: Vec<EventIdentifier> = self.unreachable.iter().copied().collect();
+        unreachable.sort_by_key(|id| id.0);
+        unreachable
+    }
+}
+
+/// Builds a [`CorrelationGraph`](struct.CorrelationGraph.html) by following the related events
+/// of an event, breadth first.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct CorrelationGraphRequest<'a> {
+    root: GenericEventIdentifier,
+    depth: usize,
+    limit: Option<usize>,
+    misp_client: &'a MISP,
+}
+
+impl CorrelationGraphRequest<'_> {
+    pub fn new(misp_client: &MISP, root: GenericEventIdentifier) -> CorrelationGraphRequest<'_> {
+        CorrelationGraphRequest {
+            root,
+            depth: 1,
+            limit: None,
+            misp_client,
+        }
+    }
+
+    /// Sets how many correlation hops are followed from the root event. Defaults to 1, which
+    /// only includes the events directly related to the root event. A depth of 0 only includes
+    /// the root event.
+    pub fn depth(&mut self, depth: usize) -> &mut Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Stops following correlations once the graph contains `limit` events. Every event of the
+    /// graph needs one request to the server, so a limit is recommended for higher depths.
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Downloads the events and builds the graph. Related events that cannot be downloaded are
+    /// listed in [`CorrelationGraph::unreachable`](struct.CorrelationGraph.html#method.unreachable)
+    /// instead of failing the whole graph.
+    pub async fn build(&self) -> MispResult<CorrelationGraph> {
+        let root = EventRequest::new(self.misp_client, self.root.clone())
+            .retrieve()
+            .await?;
+        let mut graph = CorrelationGraph::new(root.id());
+        let mut queue: VecDeque<(usize, EventIdentifier)> = VecDeque::new();
+        let mut queued: HashSet<EventIdentifier> = HashSet::new();
+        queued.insert(root.id());
+
+        let mut next = Some((0, root));
+        while let Some((distance, event)) = next.take() {
+            if distance < self.depth {
+                for related in event.related_events() {
+                    if queued.insert(related.id()) {
+                        queue.push_back((distance + 1, related.id()));
+                    }
+                }
+            }
+            graph.insert(distance, event);
+
+            if matches!(self.limit, Some(limit) if graph.len() >= limit) {
+                break;
+            }
+            while let Some((distance, id)) = queue.pop_front() {
+                match EventRequest::new(self.misp_client, GenericEventIdentifier::Local(id))
+                    .retrieve()
+                    .await
+                {
+                    Ok(event) => {
+                        next = Some((distance, event));
+                        break;
+                    }
+                    Err(MispError::NotFound(_)) | Err(MispError::Forbidden(_)) => {
+                        graph.unreachable.insert(id);
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transport::fake::{event, response, FakeTransport};
+    use crate::MispError;
+    use futures::executor::block_on;
+    use misp_types::event::EventIdentifier;
+
+    /// Serves `events/view/{id}` for the events 1 to 6. The events are related as follows, the
+    /// event 4 was deleted and the event 7 is not visible to the user:
+    ///
+    /// 1 - 2 - 5 - 6
+    /// | \ |   |
+    /// 4   3   7
+    fn transport() -> FakeTransport {
+        FakeTransport::new(|request| {
+            let id: u64 = request.url().path()["/events/view/".len()..]
+                .parse()
+                .unwrap();
+            let related: &[u64] = match id {
+                1 => &[2, 3, 4],
+                2 => &[1, 3, 5],
+                3 => &[1, 2],
+                5 => &[2, 6, 7],
+                6 => &[5],
+                4 => {
+                    return response(
+                        404,
+                        r#"{"name": "Invalid event", "message": "Invalid event"}"#,
+                    )
+                }
+                _ => return response(403, r#"{"name": "Forbidden", "message": "Forbidden"}"#),
+            };
+            response(200, event(id, related).to_string())
+        })
+    }
+
+    fn ids(ids: &[u64]) -> Vec<EventIdentifier> {
+        ids.iter().map(|id| EventIdentifier(*id)).collect()
+    }
+
+    #[test]
+    fn build_graph() {
+        let transport = transport();
+        let misp = transport.client();
+        let graph = block_on(misp.events().correlation_graph(1).depth(2).build()).unwrap();
+        assert_eq!(1, graph.root().id().0);
+        assert_eq!(4, graph.len());
+        assert_eq!(Some(1), graph.distance(EventIdentifier(3)));
+        assert_eq!(Some(2), graph.distance(EventIdentifier(5)));
+        assert!(!graph.contains(EventIdentifier(6)));
+        assert_eq!(ids(&[4]), graph.unreachable());
+        assert_eq!(
+            vec![(1, 2), (1, 3), (2, 3), (2, 5)],
+            graph
+                .edges()
+                .iter()
+                .map(|(a, b)| (a.0, b.0))
+                .collect::<Vec<_>>()
+        );
+        // Every event is downloaded once, even when several events relate to it
+        assert_eq!(5, transport.requests().len());
+
+        let graph = block_on(misp.events().correlation_graph(1).depth(5).build()).unwrap();
+        assert_eq!(5, graph.len());
+        assert_eq!(Some(3), graph.distance(EventIdentifier(6)));
+        assert_eq!(ids(&[4, 7]), graph.unreachable());
+    }
+
+    #[test]
+    fn limit_graph() {
+        let transport = transport();
+        let misp = transport.client();
+        let graph = block_on(misp.events().correlation_graph(1).depth(2).limit(3).build()).unwrap();
+        assert_eq!(3, graph.len());
+        assert_eq!(3, transport.requests().len());
+
+        let graph = block_on(misp.events().correlation_graph(1).depth(0).build()).unwrap();
+        assert_eq!(1, graph.len());
+        assert!(graph.edges().is_empty());
+    }
+
+    #[test]
+    fn unreachable_root() {
+        let misp = transport().client();
+        assert!(matches!(
+            block_on(misp.events().correlation_graph(4).build()),
+            Err(MispError::NotFound(_))
+        ));
+    }
+}
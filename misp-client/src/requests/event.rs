@@ -1,5 +1,9 @@
+use crate::requests::action::ActionResponse;
+use crate::requests::event_update::EventUpdateRequest;
 use crate::{MispResult, MISP};
-use misp_types::event::{EventFull, EventFullEmbedded, EventIdentifier, GenericEventIdentifier};
+use misp_types::event::{
+    EventFull, EventFullEmbedded, EventIdentifier, EventUpdate, GenericEventIdentifier,
+};
 use uuid::Uuid;
 
 pub enum DirectOrIndirectIdentifier {
@@ -15,7 +19,7 @@ pub struct EventRequest<'a> {
 }
 
 impl EventRequest<'_> {
-    pub fn new(misp_client: &MISP, id: GenericEventIdentifier) -> EventRequest {
+    pub fn new(misp_client: &MISP, id: GenericEventIdentifier) -> EventRequest<'_> {
         EventRequest {
             id,
             misp_client,
@@ -56,4 +60,159 @@ impl EventRequest<'_> {
             GenericEventIdentifier::Local(_) => Ok(self.cached().await?.uuid()),
         }
     }
+
+    async fn action(&mut self, action: &str) -> MispResult<ActionResponse> {
+        let response = self
+            .misp_client
+            .internal_api_call_post(
+                format!("events/{}/{}", action, self.id.to_url_id()),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.cached_local = None;
+        Ok(response)
+    }
+
+    async fn edit(
+        &mut self,
+        update: EventUpdate,
+        accepted: impl FnOnce(&EventFull) -> bool,
+        message: &str,
+    ) -> MispResult<ActionResponse> {
+        let event = EventUpdateRequest::new(self.misp_client, self.id.clone(), update)
+            .send()
+            .await?;
+        let response = ActionResponse::from_edit(
+            accepted(&event),
+            message,
+            format!("/events/edit/{}", self.id.to_url_id()),
+        );
+        self.cached_local = Some(event);
+        Ok(response)
+    }
+
+    /// Deletes the event permanently from the server, including all its attributes and objects.
+    ///
+    /// MISP does not support soft deletion for events. If you only want to hide some of the event's
+    /// content, soft delete its attributes or objects instead.
+    pub async fn delete(&mut self) -> MispResult<ActionResponse> {
+        self.action("delete").await
+    }
+
+    /// Publishes the event without sending e-mail alerts to the users of the instance.
+    pub async fn publish(&mut self) -> MispResult<ActionResponse> {
+        self.action("publish").await
+    }
+
+    /// Publishes the event and sends e-mail alerts to all the users that are eligible to see it.
+    pub async fn publish_with_alert(&mut self) -> MispResult<ActionResponse> {
+        self.action("alert").await
+    }
+
+    pub async fn unpublish(&mut self) -> MispResult<ActionResponse> {
+        self.action("unpublish").await
+    }
+
+    /// Locks the event, so that it is not modified by synchronisations anymore.
+    pub async fn lock(&mut self) -> MispResult<ActionResponse> {
+        self.set_locked(true).await
+    }
+
+    pub async fn unlock(&mut self) -> MispResult<ActionResponse> {
+        self.set_locked(false).await
+    }
+
+    async fn set_locked(&mut self, locked: bool) -> MispResult<ActionResponse> {
+        let mut update = EventUpdate::new();
+        update.locked(locked);
+        let message = if locked {
+            "Event locked."
+        } else {
+            "Event unlocked."
+        };
+        self.edit(update, |e| e.locked() == locked, message).await
+    }
+
+    /// Stops correlating the attributes of this event with the attributes of other events.
+    pub async fn disable_correlation(&mut self) -> MispResult<ActionResponse> {
+        self.set_correlation(false).await
+    }
+
+    pub async fn enable_correlation(&mut self) -> MispResult<ActionResponse> {
+        self.set_correlation(true).await
+    }
+
+    async fn set_correlation(&mut self, enabled: bool) -> MispResult<ActionResponse> {
+        let mut update = EventUpdate::new();
+        update.disable_correlation(!enabled);
+        let message = if enabled {
+            "Correlation enabled."
+        } else {
+            "Correlation disabled."
+        };
+        self.edit(update, |e| e.disable_correlation() != enabled, message)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transport::fake::{event, response, FakeTransport};
+    use futures::executor::block_on;
+
+    #[test]
+    fn actions_and_toggles() {
+        let transport = FakeTransport::new(|request| {
+            if request.url().path() == "/events/edit/7" {
+                // The event is neither locked nor excluded from the correlation
+                return response(200, event(7, &[]).to_string());
+            }
+            response(
+                200,
+                r#"{"saved": true, "success": true, "name": "Done", "message": "Done"}"#,
+            )
+        });
+        let misp = transport.client();
+        let mut event = misp.events().get(7);
+        assert!(block_on(event.delete()).unwrap().accepted());
+        assert!(block_on(event.publish()).unwrap().accepted());
+        assert!(block_on(event.publish_with_alert()).unwrap().accepted());
+        assert!(block_on(event.unpublish()).unwrap().accepted());
+        assert!(!block_on(event.lock()).unwrap().accepted());
+        assert!(block_on(event.unlock()).unwrap().accepted());
+        assert!(!block_on(event.disable_correlation()).unwrap().accepted());
+        assert!(block_on(event.enable_correlation()).unwrap().accepted());
+
+        let paths: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url().path().to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "/events/delete/7",
+                "/events/publish/7",
+                "/events/alert/7",
+                "/events/unpublish/7",
+                "/events/edit/7",
+                "/events/edit/7",
+                "/events/edit/7",
+                "/events/edit/7",
+            ],
+            paths
+        );
+        assert_eq!(
+            vec![
+                serde_json::json!({}),
+                serde_json::json!({}),
+                serde_json::json!({}),
+                serde_json::json!({}),
+                serde_json::json!({"Event": {"locked": true}}),
+                serde_json::json!({"Event": {"locked": false}}),
+                serde_json::json!({"Event": {"disable_correlation": true}}),
+                serde_json::json!({"Event": {"disable_correlation": false}}),
+            ],
+            transport.bodies()
+        );
+    }
 }
@@ -0,0 +1,29 @@
+use crate::{MispResult, MISP};
+use misp_types::event::{EventFull, EventFullEmbedded, NewEvent, NewEventEmbedded};
+
+/// Creates a new event on the server.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct EventAddRequest<'a> {
+    event: NewEventEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl EventAddRequest<'_> {
+    pub fn new(misp_client: &MISP, event: NewEvent) -> EventAddRequest<'_> {
+        EventAddRequest {
+            event: NewEventEmbedded { event },
+            misp_client,
+        }
+    }
+
+    /// Sends the event to the server. Returns the created event, including the ids and uuids
+    /// assigned by the server.
+    pub async fn send(&self) -> MispResult<EventFull> {
+        let event: EventFullEmbedded = self
+            .misp_client
+            .internal_api_call_post("events/add", &self.event)
+            .await?;
+        Ok(event.event)
+    }
+}
@@ -1,16 +1,36 @@
+use crate::requests::tag_query::TagQuery;
 use crate::{MispResult, MISP};
-use chrono::{Date, Utc};
-use misp_types::event::{EventFull, EventFullEmbedded};
+use chrono::{Date, DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use misp_types::analysis::Analysis;
+use misp_types::attribute_type::{AttributeCategory, AttributeType};
+use misp_types::event::{EventFull, EventFullEmbedded, EventIdentifier};
 use misp_types::organization::GenericOrganizationIdentifier;
+use misp_types::threat_level::ThreatLevel;
+use std::time::Duration;
+use uuid::Uuid;
 
-#[cfg(feature = "serde")]
-use misp_types::serialization_helpers::option_date_to_mispdate;
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use misp_types::serialization_helpers::{option_date_to_mispdate, option_duration_to_relative};
+use serde::{Serialize, Serializer};
 
-#[derive(Deserialize, Debug, Clone)]
-pub struct EventListResponse {
-    response: Vec<EventFullEmbedded>,
+/// A range of timestamps. A range with only a start matches everything after it.
+#[derive(Debug, Clone, Default)]
+struct TimestampRange {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+impl Serialize for TimestampRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let epoch = |date: Option<DateTime<Utc>>| date.map_or(0, |d| d.timestamp()).to_string();
+        match self.to {
+            None => serializer.serialize_str(&epoch(self.from)),
+            Some(_) => [epoch(self.from), epoch(self.to)].serialize(serializer),
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -36,8 +56,76 @@ pub struct SearchQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     info: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<AttributeType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<AttributeCategory>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<TagQuery>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threat_level_id: Option<ThreatLevel>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis: Option<Analysis>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_ids: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "option_duration_to_relative")]
+    last: Option<Duration>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<TimestampRange>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publish_timestamp: Option<TimestampRange>,
+
+    #[serde(rename = "eventid")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event: Option<EventIdentifier>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+
+    #[serde(rename = "withAttachments")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    with_attachments: Option<bool>,
+
+    #[serde(rename = "includeEventTags")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_event_tags: Option<bool>,
+
+    #[serde(rename = "includeContext")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_context: Option<bool>,
+
+    #[serde(rename = "enforceWarninglist")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enforce_warninglist: Option<bool>,
+
+    #[serde(rename = "sgReferenceOnly")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sharing_group_reference_only: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     limit: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<u64>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -54,43 +142,94 @@ impl SearchQuery {
             info: None,
             limit: None,
             before: None,
+            value: None,
+            kind: None,
+            category: None,
+            tags: None,
+            threat_level_id: None,
+            analysis: None,
+            published: None,
+            to_ids: None,
+            last: None,
+            timestamp: None,
+            publish_timestamp: None,
+            event: None,
+            uuid: None,
+            with_attachments: None,
+            include_event_tags: None,
+            include_context: None,
+            enforce_warninglist: None,
+            sharing_group_reference_only: None,
+            deleted: None,
+            page: None,
         }
     }
 }
+
+impl Default for SearchQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 // The Request's lifetime is bound to the client's lifetime
 pub struct EventListRequest<'a> {
     search_query: Option<EmbeddedSearchQuery>,
     misp_client: &'a MISP,
     cached_local: Option<Vec<EventFull>>,
+    page_size: u64,
+    concurrency: usize,
+}
+
+/// Starts downloading one page of a search. The events of the page are deserialized while they
+/// are received.
+async fn open_page(
+    misp_client: &MISP,
+    mut query: EmbeddedSearchQuery,
+    page: u64,
+    page_size: u64,
+) -> MispResult<impl Stream<Item = MispResult<EventFull>>> {
+    query.request.page = Some(page);
+    query.request.limit = Some(page_size);
+    let events = misp_client
+        .internal_api_call_search_stream("events/restSearch", &query, &["response"])
+        .await?;
+    Ok(events.map(|e: MispResult<EventFullEmbedded>| e.map(|e| e.event)))
 }
 
 impl EventListRequest<'_> {
-    pub fn new(misp_client: &MISP, search_query: Option<EmbeddedSearchQuery>) -> EventListRequest {
+    pub fn new(
+        misp_client: &MISP,
+        search_query: Option<EmbeddedSearchQuery>,
+    ) -> EventListRequest<'_> {
         EventListRequest {
             search_query,
             misp_client,
             cached_local: None,
+            page_size: 100,
+            concurrency: 1,
         }
     }
 
     async fn download_to_cache(&mut self) -> MispResult<Vec<EventFull>> {
-        let event_list: EventListResponse = match &self.search_query {
+        // The events are deserialized while they are received, so the body is never held in
+        // memory along with the events
+        let events: Vec<EventFullEmbedded> = match &self.search_query {
             Some(query) => {
                 self.misp_client
-                    .internal_api_call_post(format!("events/restSearch"), query)
+                    .internal_api_call_search_stream("events/restSearch", query, &["response"])
+                    .await?
+                    .try_collect()
                     .await?
             }
             None => {
                 self.misp_client
-                    .internal_api_call_get(format!("events"))
+                    .internal_api_call_get_stream("events", &["response"])
+                    .await?
+                    .try_collect()
                     .await?
             }
         };
-        Ok(event_list
-            .response
-            .iter()
-            .map(|e| e.event.clone())
-            .collect())
+        Ok(events.into_iter().map(|e| e.event).collect())
     }
 
     async fn cached(&mut self) -> MispResult<&Vec<EventFull>> {
@@ -100,6 +239,31 @@ impl EventListRequest<'_> {
         Ok(self.cached_local.as_ref().unwrap())
     }
 
+    /// Sets how many events are downloaded per request by [`stream`](#method.stream). Defaults
+    /// to 100.
+    pub fn page_size(&mut self, page_size: u64) -> &mut Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Sets how many pages [`stream`](#method.stream) downloads at the same time. Defaults to 1.
+    ///
+    /// With a higher concurrency, a few pages past the end of the results may be requested
+    /// before the stream notices that it reached the end.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    fn search_query(&mut self) -> &mut SearchQuery {
+        &mut self
+            .search_query
+            .get_or_insert(EmbeddedSearchQuery {
+                request: SearchQuery::new(),
+            })
+            .request
+    }
+
     /// Downloads all the events matching the set filters
     pub async fn retrieve(&mut self) -> MispResult<Vec<EventFull>> {
         Ok(self.cached().await?.clone())
@@ -110,10 +274,7 @@ impl EventListRequest<'_> {
         &mut self,
         organization: impl Into<GenericOrganizationIdentifier>,
     ) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
-            request: SearchQuery::new(),
-        });
-        search_query.request.organization = Some(organization.into());
+        self.search_query().organization = Some(organization.into());
         self
     }
 
@@ -123,10 +284,7 @@ impl EventListRequest<'_> {
     /// If you only want to find events with the exact same event info, use
     /// [`with_exact_info`](#method.with_exact_info). It will not take substrings into consideration.
     pub fn containing_info(&mut self, search: impl AsRef<str>) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
-            request: SearchQuery::new(),
-        });
-        search_query.request.info = Some(format!("%{}%", search.as_ref()));
+        self.search_query().info = Some(format!("%{}%", search.as_ref()));
         self
     }
 
@@ -135,37 +293,377 @@ impl EventListRequest<'_> {
     /// If you also want to find events where the search query is only a substring of the actual
     /// event info, use [`containing_info`](#method.containing_info) instead.
     pub fn with_exact_info(&mut self, search: impl Into<String>) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
-            request: SearchQuery::new(),
-        });
-        search_query.request.info = Some(search.into());
+        self.search_query().info = Some(search.into());
         self
     }
 
     /// Filters events that happened after a specific date.
     pub fn after(&mut self, date: Date<Utc>) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
-            request: SearchQuery::new(),
-        });
-        search_query.request.after = Some(date);
+        self.search_query().after = Some(date);
         self
     }
 
     /// Filters events that happened before a specific date.
     pub fn before(&mut self, date: Date<Utc>) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
-            request: SearchQuery::new(),
-        });
-        search_query.request.before = Some(date);
+        self.search_query().before = Some(date);
         self
     }
 
-    /// Limits the amount of results
+    /// Limits the amount of results. When the events are [streamed](#method.stream), the
+    /// stream ends after this amount of events.
     pub fn limit(&mut self, limit: u64) -> &mut Self {
-        let search_query = self.search_query.get_or_insert(EmbeddedSearchQuery {
+        self.search_query().limit = Some(limit);
+        self
+    }
+
+    /// Filters events that contain an attribute with a specific value.
+    pub fn with_value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.search_query().value = Some(value.into());
+        self
+    }
+
+    /// Filters events that contain an attribute whose value contains a specific text.
+    pub fn containing_value(&mut self, search: impl AsRef<str>) -> &mut Self {
+        self.search_query().value = Some(format!("%{}%", search.as_ref()));
+        self
+    }
+
+    /// Filters events that contain an attribute of a specific MISP type (e.g. `ip-dst`).
+    pub fn with_attribute_kind(&mut self, kind: AttributeType) -> &mut Self {
+        self.search_query().kind = Some(kind);
+        self
+    }
+
+    /// Filters events that contain an attribute of a specific category.
+    pub fn with_attribute_category(&mut self, category: AttributeCategory) -> &mut Self {
+        self.search_query().category = Some(category);
+        self
+    }
+
+    /// Filters events by their tags. When called several times, the conditions of all the
+    /// queries are combined.
+    pub fn matching_tags(&mut self, query: TagQuery) -> &mut Self {
+        self.search_query()
+            .tags
+            .get_or_insert_with(TagQuery::default)
+            .merge(query);
+        self
+    }
+
+    /// Filters events that have a specific tag. When called several times, the events need to
+    /// have all of the tags.
+    pub fn with_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.matching_tags(TagQuery::all_of(Some(name)))
+    }
+
+    /// Filters events that have at least one of the given tags.
+    pub fn with_any_tag(
+        &mut self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> &mut Self {
+        self.matching_tags(TagQuery::any_of(names))
+    }
+
+    /// Excludes the events that have a specific tag.
+    pub fn without_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.matching_tags(TagQuery::none_of(Some(name)))
+    }
+
+    pub fn with_threat_level(&mut self, threat_level: ThreatLevel) -> &mut Self {
+        self.search_query().threat_level_id = Some(threat_level);
+        self
+    }
+
+    pub fn with_analysis(&mut self, analysis: Analysis) -> &mut Self {
+        self.search_query().analysis = Some(analysis);
+        self
+    }
+
+    /// Filters events based on whether they are published.
+    pub fn published(&mut self, published: bool) -> &mut Self {
+        self.search_query().published = Some(published);
+        self
+    }
+
+    /// Filters events that contain attributes with the given IDS flag.
+    pub fn to_ids(&mut self, to_ids: bool) -> &mut Self {
+        self.search_query().to_ids = Some(to_ids);
+        self
+    }
+
+    /// Filters events that were published within the given duration (e.g. the last 7 days).
+    /// The duration is sent with a precision of one second. Durations shorter than one second make
+    /// the request fail with a [`JsonError`](../../enum.MispError.html#variant.JsonError).
+    pub fn last(&mut self, duration: Duration) -> &mut Self {
+        self.search_query().last = Some(duration);
+        self
+    }
+
+    /// Filters events that were modified after a specific moment.
+    pub fn modified_after(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        let query = self.search_query();
+        query
+            .timestamp
+            .get_or_insert_with(TimestampRange::default)
+            .from = Some(timestamp);
+        self
+    }
+
+    /// Filters events that were modified before a specific moment.
+    pub fn modified_before(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        let query = self.search_query();
+        query
+            .timestamp
+            .get_or_insert_with(TimestampRange::default)
+            .to = Some(timestamp);
+        self
+    }
+
+    /// Filters events that were published after a specific moment.
+    pub fn published_after(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        let query = self.search_query();
+        query
+            .publish_timestamp
+            .get_or_insert_with(TimestampRange::default)
+            .from = Some(timestamp);
+        self
+    }
+
+    /// Filters events that were published before a specific moment.
+    pub fn published_before(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        let query = self.search_query();
+        query
+            .publish_timestamp
+            .get_or_insert_with(TimestampRange::default)
+            .to = Some(timestamp);
+        self
+    }
+
+    /// Only returns the event with a specific id.
+    pub fn with_id(&mut self, event: EventIdentifier) -> &mut Self {
+        self.search_query().event = Some(event);
+        self
+    }
+
+    /// Only returns the event with a specific uuid.
+    pub fn with_uuid(&mut self, uuid: Uuid) -> &mut Self {
+        self.search_query().uuid = Some(uuid);
+        self
+    }
+
+    /// Includes the content of attachments (e.g. malware samples) in the events.
+    pub fn with_attachments(&mut self) -> &mut Self {
+        self.search_query().with_attachments = Some(true);
+        self
+    }
+
+    /// Includes the tags of the events in the results.
+    pub fn include_event_tags(&mut self) -> &mut Self {
+        self.search_query().include_event_tags = Some(true);
+        self
+    }
+
+    /// Includes the context of the event (e.g. its tags and galaxies) with every attribute.
+    pub fn include_context(&mut self) -> &mut Self {
+        self.search_query().include_context = Some(true);
+        self
+    }
+
+    /// Excludes the attributes that match a warninglist (e.g. known benign values).
+    pub fn enforce_warninglist(&mut self) -> &mut Self {
+        self.search_query().enforce_warninglist = Some(true);
+        self
+    }
+
+    /// Only returns the identifiers of sharing groups instead of the full sharing groups.
+    pub fn sharing_group_reference_only(&mut self) -> &mut Self {
+        self.search_query().sharing_group_reference_only = Some(true);
+        self
+    }
+
+    /// Also returns soft deleted attributes.
+    pub fn include_deleted(&mut self) -> &mut Self {
+        self.search_query().deleted = Some(true);
+        self
+    }
+}
+
+impl<'a> EventListRequest<'a> {
+    /// Downloads the events matching the set filters page by page and yields them one at a time,
+    /// in the order of the server. Unlike [`retrieve`](#method.retrieve), the events are not
+    /// cached and every event is deserialized as soon as it is received, so memory use does not
+    /// depend on the size of the results.
+    ///
+    /// The stream ends after the first page that contains less events than the
+    /// [page size](#method.page_size), or after the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// use futures::StreamExt;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// let mut list = misp.events().list();
+    /// list.with_tag("tlp:white").page_size(500).concurrency(4);
+    /// let mut events = list.stream();
+    /// while let Some(event) = events.next().await {
+    ///     println!("{}", event?.info());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(&self) -> impl Stream<Item = MispResult<EventFull>> + Unpin + 'a {
+        let misp_client = self.misp_client;
+        let page_size = self.page_size;
+        let query = self.search_query.clone().unwrap_or(EmbeddedSearchQuery {
             request: SearchQuery::new(),
         });
-        search_query.request.limit = Some(limit);
-        self
+        let limit = query.request.limit.map_or(usize::MAX, |l| l as usize);
+
+        let pages = stream::iter(1..)
+            .map(move |page| open_page(misp_client, query.clone(), page, page_size))
+            .buffered(self.concurrency);
+
+        // (pages, page being read, events read from that page, done)
+        let state = (Box::pin(pages), None, 0, false);
+        let events = stream::unfold(
+            state,
+            move |(mut pages, mut current, mut count, mut done)| async move {
+                loop {
+                    if done {
+                        return None;
+                    }
+                    match current.as_mut() {
+                        None => match pages.next().await {
+                            Some(Ok(events)) => {
+                                current = Some(Box::pin(events));
+                                count = 0;
+                            }
+                            Some(Err(e)) => return Some((Err(e), (pages, current, count, true))),
+                            None => return None,
+                        },
+                        Some(events) => match events.next().await {
+                            Some(Ok(event)) => {
+                                count += 1;
+                                return Some((Ok(event), (pages, current, count, done)));
+                            }
+                            Some(Err(e)) => return Some((Err(e), (pages, current, count, true))),
+                            // A short page is the last one
+                            None => {
+                                done = count < page_size;
+                                current = None;
+                            }
+                        },
+                    }
+                }
+            },
+        );
+        Box::pin(events.take(limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmbeddedSearchQuery, EventListRequest, SearchQuery};
+    use crate::transport::fake::{chunked_response, event, response, FakeTransport};
+    use crate::MISP;
+    use chrono::{TimeZone, Utc};
+    use futures::executor::{block_on, block_on_stream};
+    use std::time::Duration;
+
+    #[test]
+    fn search_query_to_json() {
+        let misp = MISP::new("https://test.xyz/", "12345678");
+        let mut request = EventListRequest::new(&misp, None);
+        request
+            .with_tag("tlp:amber")
+            .with_tag("ransomware")
+            .without_tag("false-positive")
+            .last(Duration::from_secs(7 * 86400))
+            .modified_after(Utc.timestamp_opt(1596744000, 0).unwrap())
+            .published_after(Utc.timestamp_opt(1596744000, 0).unwrap())
+            .published_before(Utc.timestamp_opt(1596830400, 0).unwrap())
+            .include_context();
+        let query: &EmbeddedSearchQuery = request.search_query.as_ref().unwrap();
+        assert_eq!(
+            serde_json::json!({"request": {
+                "returnFormat": "json",
+                "tags": {"AND": ["tlp:amber", "ransomware"], "NOT": ["false-positive"]},
+                "last": "7d",
+                "timestamp": "1596744000",
+                "publish_timestamp": ["1596744000", "1596830400"],
+                "includeContext": true
+            }}),
+            serde_json::to_value(query).unwrap()
+        );
+        assert_eq!(
+            serde_json::json!({"returnFormat": "json"}),
+            serde_json::to_value(SearchQuery::new()).unwrap()
+        );
+    }
+
+    /// Serves 5 events, page by page.
+    fn paged_transport() -> FakeTransport {
+        FakeTransport::new(|request| {
+            let query: serde_json::Value = serde_json::from_slice(request.body().unwrap()).unwrap();
+            let page = query["request"]["page"].as_u64().unwrap();
+            let limit = query["request"]["limit"].as_u64().unwrap();
+            let events: Vec<serde_json::Value> = ((page - 1) * limit + 1..=page * limit)
+                .take_while(|id| *id <= 5)
+                .map(|id| event(id, &[]))
+                .collect();
+            response(200, serde_json::json!({ "response": events }).to_string())
+        })
+    }
+
+    #[test]
+    fn stream_pages() {
+        let transport = paged_transport();
+        let misp = transport.client();
+        let mut request = misp.events().list();
+        request.page_size(2);
+        let ids: Vec<u64> = block_on_stream(request.stream())
+            .map(|e| e.unwrap().id().0)
+            .collect();
+        assert_eq!(vec![1, 2, 3, 4, 5], ids);
+        // The third page is short, so no fourth page is requested
+        let pages: Vec<(u64, u64)> = transport
+            .bodies()
+            .iter()
+            .map(|b| {
+                let request = &b["request"];
+                (
+                    request["page"].as_u64().unwrap(),
+                    request["limit"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(vec![(1, 2), (2, 2), (3, 2)], pages);
+
+        let transport = paged_transport();
+        let misp = transport.client();
+        let mut request = misp.events().list();
+        request.page_size(2).limit(3);
+        assert_eq!(3, block_on_stream(request.stream()).count());
+        assert_eq!(2, transport.requests().len());
+    }
+
+    #[test]
+    fn retrieve_chunked_body() {
+        let transport = FakeTransport::new(|_| {
+            let events: Vec<serde_json::Value> = (1..=3).map(|id| event(id, &[2])).collect();
+            chunked_response(serde_json::json!({ "response": events }).to_string(), 7)
+        });
+        let misp = transport.client();
+        let events = block_on(misp.events().list().retrieve()).unwrap();
+        assert_eq!(3, events.len());
+        assert_eq!(3, events[2].id().0);
+        assert_eq!("/events", transport.requests()[0].url().path());
+
+        let events = block_on(misp.events().list().with_tag("tlp:white").retrieve()).unwrap();
+        assert_eq!(3, events.len());
+        assert_eq!("/events/restSearch", transport.requests()[1].url().path());
     }
 }
@@ -0,0 +1,38 @@
+use crate::{MispResult, MISP};
+use misp_types::event::{
+    EventFull, EventFullEmbedded, EventUpdate, EventUpdateEmbedded, GenericEventIdentifier,
+};
+
+/// Edits an existing event on the server. Only the fields set in the
+/// [`EventUpdate`](../../misp_types/event/struct.EventUpdate.html) are sent.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct EventUpdateRequest<'a> {
+    id: GenericEventIdentifier,
+    update: EventUpdateEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl EventUpdateRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        id: GenericEventIdentifier,
+        update: EventUpdate,
+    ) -> EventUpdateRequest<'_> {
+        EventUpdateRequest {
+            id,
+            update: EventUpdateEmbedded { event: update },
+            misp_client,
+        }
+    }
+
+    /// Sends the changes to the server. Returns the event as it is stored on the server after the
+    /// update.
+    pub async fn send(&self) -> MispResult<EventFull> {
+        let event: EventFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(format!("events/edit/{}", self.id.to_url_id()), &self.update)
+            .await?;
+        Ok(event.event)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{MispResult, MISP};
+use misp_types::galaxy::{
+    Galaxy, GalaxyCluster, GalaxyClusterEmbedded, GalaxyEmbedded, GalaxyIdentifier,
+    GenericGalaxyIdentifier, NewGalaxyCluster, NewGalaxyClusterEmbedded,
+};
+use uuid::Uuid;
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct GalaxyRequest<'a> {
+    id: GenericGalaxyIdentifier,
+    misp_client: &'a MISP,
+    cached_local: Option<Galaxy>,
+}
+
+impl GalaxyRequest<'_> {
+    pub fn new(misp_client: &MISP, id: GenericGalaxyIdentifier) -> GalaxyRequest<'_> {
+        GalaxyRequest {
+            id,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Galaxy> {
+        let galaxy: GalaxyEmbedded = self
+            .misp_client
+            .internal_api_call_get(format!("galaxies/view/{}", self.id.to_url_id()))
+            .await?;
+        Ok(galaxy.into_galaxy())
+    }
+
+    async fn cached(&mut self) -> MispResult<&Galaxy> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    /// Retrieves the galaxy, including all of its clusters.
+    pub async fn retrieve(&mut self) -> MispResult<Galaxy> {
+        let galaxy_ref = self.cached().await?;
+        Ok(galaxy_ref.clone())
+    }
+
+    pub async fn id(&mut self) -> MispResult<GalaxyIdentifier> {
+        match self.id {
+            GenericGalaxyIdentifier::Global(_) => Ok(self.cached().await?.id()),
+            GenericGalaxyIdentifier::Local(id) => Ok(id),
+        }
+    }
+
+    pub async fn uuid(&mut self) -> MispResult<Uuid> {
+        match self.id {
+            GenericGalaxyIdentifier::Global(uuid) => Ok(uuid),
+            GenericGalaxyIdentifier::Local(_) => Ok(self.cached().await?.uuid()),
+        }
+    }
+
+    /// Searches the clusters of the galaxy. The search term is matched against the value, the
+    /// description and the synonyms of the clusters. To search the clusters of all galaxies, use
+    /// [`GalaxiesApi::search_clusters`](../api/struct.GalaxiesApi.html#method.search_clusters).
+    pub async fn search_clusters(
+        &mut self,
+        search: impl Into<String>,
+    ) -> MispResult<Vec<GalaxyCluster>> {
+        let id = self.id().await?;
+        let clusters: Vec<GalaxyClusterEmbedded> = self
+            .misp_client
+            .internal_api_call_search(
+                format!("galaxy_clusters/index/{}", id),
+                &serde_json::json!({ "searchall": search.into() }),
+            )
+            .await?;
+        Ok(clusters.into_iter().map(|c| c.cluster).collect())
+    }
+
+    /// Adds a new custom cluster to the galaxy. Returns the created cluster.
+    pub async fn add_cluster(&mut self, cluster: NewGalaxyCluster) -> MispResult<GalaxyCluster> {
+        let id = self.id().await?;
+        let cluster: GalaxyClusterEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("galaxy_clusters/add/{}", id),
+                &NewGalaxyClusterEmbedded { cluster },
+            )
+            .await?;
+        self.cached_local = None;
+        Ok(cluster.cluster)
+    }
+
+    /// Exports the custom clusters of the galaxy, including their meta information and relations.
+    /// The exported clusters can be imported on another server with
+    /// [`GalaxiesApi::import`](../api/struct.GalaxiesApi.html#method.import).
+    pub async fn export(&mut self) -> MispResult<Vec<GalaxyCluster>> {
+        let id = self.id().await?;
+        let clusters: Vec<GalaxyClusterEmbedded> = self
+            .misp_client
+            .internal_api_call_search(
+                format!("galaxies/export/{}", id),
+                &serde_json::json!({"Galaxy": {
+                    "default": false,
+                    "custom": true,
+                    "distribution": [0, 1, 2, 3, 4],
+                    "format": "misp",
+                }}),
+            )
+            .await?;
+        Ok(clusters.into_iter().map(|c| c.cluster).collect())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::requests::action::ActionResponse;
+use crate::{MispResult, MISP};
+use misp_types::galaxy::GalaxyClusterIdentifier;
+
+/// Attaches a galaxy cluster to an event or an attribute.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct GalaxyAttachRequest<'a> {
+    cluster: GalaxyClusterIdentifier,
+    target_type: &'static str,
+    target: String,
+    local: bool,
+    misp_client: &'a MISP,
+}
+
+impl<'a> GalaxyAttachRequest<'a> {
+    /// `target_type` is either `event` or `attribute`, `target` is the id or the uuid of the
+    /// event or attribute.
+    pub(crate) fn new(
+        misp_client: &'a MISP,
+        cluster: GalaxyClusterIdentifier,
+        target_type: &'static str,
+        target: String,
+    ) -> GalaxyAttachRequest<'a> {
+        GalaxyAttachRequest {
+            cluster,
+            target_type,
+            target,
+            local: false,
+            misp_client,
+        }
+    }
+
+    /// Attaches the cluster as local tag. Local tags are only visible on this instance and are
+    /// never synchronised to other instances.
+    pub fn local(&mut self) -> &mut Self {
+        self.local = true;
+        self
+    }
+
+    pub async fn send(&self) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post(
+                format!(
+                    "galaxies/attachCluster/{}/{}/local:{}",
+                    self.target, self.target_type, self.local as u8
+                ),
+                &serde_json::json!({"Galaxy": {"target_id": self.cluster.to_string()}}),
+            )
+            .await
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{MispResult, MISP};
+use misp_types::galaxy::{GalaxyCluster, GalaxyClusterEmbedded};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GalaxyClusterListResponse {
+    response: Vec<GalaxyClusterEmbedded>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GalaxyClusterSearchQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+
+    /// The meta information of the clusters (e.g. `synonyms`), keyed by its name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    elements: BTreeMap<String, String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    galaxy_uuid: Option<Uuid>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct EmbeddedGalaxyClusterSearchQuery {
+    request: GalaxyClusterSearchQuery,
+}
+
+/// Searches the clusters of all galaxies (`galaxy_clusters/restSearch`). Use
+/// [`GalaxyRequest::search_clusters`](../galaxy/struct.GalaxyRequest.html#method.search_clusters)
+/// to search the clusters of a single galaxy.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct GalaxyClusterListRequest<'a> {
+    search_query: EmbeddedGalaxyClusterSearchQuery,
+    misp_client: &'a MISP,
+    cached_local: Option<Vec<GalaxyCluster>>,
+}
+
+impl GalaxyClusterListRequest<'_> {
+    pub fn new(misp_client: &MISP) -> GalaxyClusterListRequest<'_> {
+        GalaxyClusterListRequest {
+            search_query: EmbeddedGalaxyClusterSearchQuery {
+                request: GalaxyClusterSearchQuery::default(),
+            },
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Vec<GalaxyCluster>> {
+        let clusters: GalaxyClusterListResponse = self
+            .misp_client
+            .internal_api_call_search("galaxy_clusters/restSearch", &self.search_query)
+            .await?;
+        Ok(clusters.response.into_iter().map(|c| c.cluster).collect())
+    }
+
+    async fn cached(&mut self) -> MispResult<&Vec<GalaxyCluster>> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    /// Downloads all the clusters matching the set filters
+    pub async fn retrieve(&mut self) -> MispResult<Vec<GalaxyCluster>> {
+        Ok(self.cached().await?.clone())
+    }
+
+    /// Filters clusters that have a specific value (e.g. `Sofacy`). `%` can be used as wildcard.
+    pub fn with_value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.search_query.request.value = Some(value.into());
+        self
+    }
+
+    /// Filters clusters that list a specific synonym (e.g. `APT28`).
+    pub fn with_synonym(&mut self, synonym: impl Into<String>) -> &mut Self {
+        self.search_query
+            .request
+            .elements
+            .insert("synonyms".into(), synonym.into());
+        self
+    }
+
+    /// Only returns the clusters of a specific galaxy.
+    pub fn in_galaxy(&mut self, galaxy: Uuid) -> &mut Self {
+        self.search_query.request.galaxy_uuid = Some(galaxy);
+        self
+    }
+
+    /// Filters clusters by the name of their tag (e.g.
+    /// `misp-galaxy:threat-actor="Sofacy"`).
+    pub fn with_tag_name(&mut self, tag_name: impl Into<String>) -> &mut Self {
+        self.search_query.request.tag_name = Some(tag_name.into());
+        self
+    }
+
+    /// Limits the amount of results
+    pub fn limit(&mut self, limit: u64) -> &mut Self {
+        self.search_query.request.limit = Some(limit);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GalaxyClusterListRequest;
+    use crate::MISP;
+    use uuid::Uuid;
+
+    #[test]
+    fn search_query_to_json() {
+        let misp = MISP::new("https://test.xyz/", "12345678");
+        let mut request = GalaxyClusterListRequest::new(&misp);
+        request
+            .with_synonym("APT28")
+            .in_galaxy(Uuid::parse_str("698774c7-8022-42c4-917f-8d6e4f06ada3").unwrap())
+            .limit(10);
+        assert_eq!(
+            serde_json::json!({"request": {
+                "elements": {"synonyms": "APT28"},
+                "galaxy_uuid": "698774c7-8022-42c4-917f-8d6e4f06ada3",
+                "limit": 10
+            }}),
+            serde_json::to_value(&request.search_query).unwrap()
+        );
+
+        let mut request = GalaxyClusterListRequest::new(&misp);
+        request.with_value("Sofacy");
+        assert_eq!(
+            serde_json::json!({"request": {"value": "Sofacy"}}),
+            serde_json::to_value(&request.search_query).unwrap()
+        );
+    }
+}
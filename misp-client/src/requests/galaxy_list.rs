@@ -0,0 +1,39 @@
+use crate::{MispResult, MISP};
+use misp_types::galaxy::{Galaxy, GalaxyEmbedded};
+
+/// Lists the galaxies known to the server. The clusters of the galaxies are not included.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct GalaxyListRequest<'a> {
+    misp_client: &'a MISP,
+    cached_local: Option<Vec<Galaxy>>,
+}
+
+impl GalaxyListRequest<'_> {
+    pub fn new(misp_client: &MISP) -> GalaxyListRequest<'_> {
+        GalaxyListRequest {
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Vec<Galaxy>> {
+        let galaxies: Vec<GalaxyEmbedded> = self
+            .misp_client
+            .internal_api_call_get("galaxies/index")
+            .await?;
+        Ok(galaxies.into_iter().map(|g| g.into_galaxy()).collect())
+    }
+
+    async fn cached(&mut self) -> MispResult<&Vec<Galaxy>> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<Vec<Galaxy>> {
+        let galaxies_ref = self.cached().await?;
+        Ok(galaxies_ref.clone())
+    }
+}
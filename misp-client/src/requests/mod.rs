@@ -1,4 +1,27 @@
 //! This module contains the types and functions required to communicate with the misp server instance.
+pub mod action;
 pub mod api;
+pub mod attribute;
+pub mod attribute_add;
+pub mod attribute_list;
+pub mod attribute_update;
+pub mod correlation;
 pub mod event;
+pub mod event_add;
 pub mod event_list;
+pub mod event_update;
+pub mod galaxy;
+pub mod galaxy_attach;
+pub mod galaxy_cluster_list;
+pub mod galaxy_list;
+pub mod object;
+pub mod object_add;
+pub mod object_update;
+pub mod proposal;
+pub mod proposal_add;
+pub mod proposal_edit;
+pub mod proposal_list;
+pub mod tag_add;
+pub mod tag_attach;
+pub mod tag_list;
+pub mod tag_query;
@@ -0,0 +1,259 @@
+use crate::requests::action::ActionResponse;
+use crate::requests::object_update::ObjectUpdateRequest;
+use crate::{MispResult, MISP};
+use misp_types::object::{
+    GenericObjectIdentifier, NewObjectReference, NewObjectReferenceEmbedded, ObjectFull,
+    ObjectFullEmbedded, ObjectIdentifier, ObjectReference, ObjectReferenceEmbedded, ObjectUpdate,
+};
+use uuid::Uuid;
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ObjectRequest<'a> {
+    id: GenericObjectIdentifier,
+    misp_client: &'a MISP,
+    cached_local: Option<ObjectFull>,
+}
+
+impl ObjectRequest<'_> {
+    pub fn new(misp_client: &MISP, id: GenericObjectIdentifier) -> ObjectRequest<'_> {
+        ObjectRequest {
+            id,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<ObjectFull> {
+        let object: ObjectFullEmbedded = self
+            .misp_client
+            .internal_api_call_get(format!("objects/view/{}", self.id.to_url_id()))
+            .await?;
+        Ok(object.object)
+    }
+
+    async fn cached(&mut self) -> MispResult<&ObjectFull> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<ObjectFull> {
+        let object_ref = self.cached().await?;
+        Ok(object_ref.clone())
+    }
+
+    pub async fn id(&mut self) -> MispResult<ObjectIdentifier> {
+        match self.id {
+            GenericObjectIdentifier::Global(_) => Ok(self.cached().await?.id()),
+            GenericObjectIdentifier::Local(id) => Ok(id),
+        }
+    }
+
+    pub async fn uuid(&mut self) -> MispResult<Uuid> {
+        match self.id {
+            GenericObjectIdentifier::Global(uuid) => Ok(uuid),
+            GenericObjectIdentifier::Local(_) => Ok(self.cached().await?.uuid()),
+        }
+    }
+
+    /// Edits the object. Only the fields that are set in `update` are changed on the server.
+    pub async fn edit(&mut self, update: ObjectUpdate) -> MispResult<ObjectFull> {
+        let object = ObjectUpdateRequest::new(self.misp_client, self.id.clone(), update)
+            .send()
+            .await?;
+        self.cached_local = Some(object.clone());
+        Ok(object)
+    }
+
+    async fn delete_internal(&mut self, hard: bool) -> MispResult<ActionResponse> {
+        let endpoint = if hard {
+            format!("objects/delete/{}/1", self.id.to_url_id())
+        } else {
+            format!("objects/delete/{}", self.id.to_url_id())
+        };
+        let response = self
+            .misp_client
+            .internal_api_call_post(endpoint, &serde_json::json!({}))
+            .await?;
+        self.cached_local = None;
+        Ok(response)
+    }
+
+    /// Soft deletes the object and its attributes: they are flagged as deleted, but stay on the
+    /// server.
+    pub async fn delete(&mut self) -> MispResult<ActionResponse> {
+        self.delete_internal(false).await
+    }
+
+    /// Deletes the object and its attributes permanently from the server.
+    pub async fn hard_delete(&mut self) -> MispResult<ActionResponse> {
+        self.delete_internal(true).await
+    }
+
+    /// Adds a reference from this object to another object or attribute of the same event.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use misp_client::{MISP, MispResult};
+    /// # use uuid::Uuid;
+    /// # #[async_std::main]
+    /// # async fn main() -> MispResult<()>  {
+    /// # let misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+    /// # let dropped_file = Uuid::new_v4();
+    /// let reference = misp
+    ///     .objects()
+    ///     .get(42)
+    ///     .add_reference(dropped_file, "drops")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_reference(
+        &mut self,
+        referenced_uuid: Uuid,
+        relationship_type: impl Into<String>,
+    ) -> MispResult<ObjectReference> {
+        let reference = NewObjectReference::new(
+            self.uuid().await?,
+            referenced_uuid,
+            relationship_type.into(),
+        );
+        self.add_new_reference(reference).await
+    }
+
+    /// Adds a reference that was built locally, e.g. to set a comment on it.
+    pub async fn add_new_reference(
+        &mut self,
+        reference: NewObjectReference,
+    ) -> MispResult<ObjectReference> {
+        let reference: ObjectReferenceEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                "objectReferences/add",
+                &NewObjectReferenceEmbedded { reference },
+            )
+            .await?;
+        self.cached_local = None;
+        Ok(reference.reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transport::fake::{response, FakeTransport};
+    use futures::executor::block_on;
+    use misp_types::object::NewObject;
+    use uuid::Uuid;
+
+    const OBJECT: &str = r#"{"Object": {
+        "id": "21", "name": "file", "meta-category": "file", "description": "File object",
+        "template_uuid": "688c46fb-5edb-40a3-8273-1af7923e2215", "template_version": "22",
+        "event_id": "7", "uuid": "00000000-0000-4000-8000-000000000021",
+        "timestamp": "1590000000", "distribution": "5", "sharing_group_id": "0", "comment": "",
+        "deleted": false, "ObjectReference": [], "Attribute": []
+    }}"#;
+
+    const REFERENCE: &str = r#"{"ObjectReference": {
+        "id": "31", "uuid": "00000000-0000-4000-8000-000000000031", "timestamp": "1590000000",
+        "object_id": "21", "object_uuid": "00000000-0000-4000-8000-000000000021",
+        "referenced_uuid": "00000000-0000-4000-8000-000000000022", "referenced_type": "1",
+        "relationship_type": "drops", "comment": "", "deleted": false
+    }}"#;
+
+    fn transport() -> FakeTransport {
+        FakeTransport::new(|request| match request.url().path() {
+            "/objects/view/21" | "/objects/add/7" => response(200, OBJECT),
+            "/objectReferences/add" => response(200, REFERENCE),
+            _ => response(
+                200,
+                r#"{"saved": true, "success": true, "message": "Deleted"}"#,
+            ),
+        })
+    }
+
+    fn paths(transport: &FakeTransport) -> Vec<String> {
+        transport
+            .requests()
+            .iter()
+            .map(|r| r.url().path().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn add_and_delete_objects() {
+        let transport = transport();
+        let misp = transport.client();
+        let object = block_on(
+            misp.objects()
+                .add(7, NewObject::file("invoice.pdf.exe"))
+                .send(),
+        )
+        .unwrap();
+        assert_eq!(21, object.id().0);
+        let body = &transport.bodies()[0];
+        assert_eq!("file", body["name"]);
+        assert_eq!("filename", body["Attribute"][0]["object_relation"]);
+        assert_eq!("invoice.pdf.exe", body["Attribute"][0]["value"]);
+
+        assert!(block_on(misp.objects().get(21).delete())
+            .unwrap()
+            .accepted());
+        assert!(block_on(misp.objects().get(21).hard_delete())
+            .unwrap()
+            .accepted());
+        assert_eq!(
+            vec![
+                "/objects/add/7",
+                "/objects/delete/21",
+                "/objects/delete/21/1"
+            ],
+            paths(&transport)
+        );
+    }
+
+    #[test]
+    fn add_and_delete_references() {
+        let object = Uuid::parse_str("00000000-0000-4000-8000-000000000021").unwrap();
+        let dropped = Uuid::parse_str("00000000-0000-4000-8000-000000000022").unwrap();
+        let expected_body = serde_json::json!({"ObjectReference": {
+            "object_uuid": "00000000-0000-4000-8000-000000000021",
+            "referenced_uuid": "00000000-0000-4000-8000-000000000022",
+            "relationship_type": "drops"
+        }});
+
+        // The uuid of an object identified by its id is looked up first
+        let transport = transport();
+        let misp = transport.client();
+        let reference = block_on(misp.objects().get(21).add_reference(dropped, "drops")).unwrap();
+        assert_eq!(dropped, reference.referenced_uuid());
+        assert_eq!(
+            vec!["/objects/view/21", "/objectReferences/add"],
+            paths(&transport)
+        );
+        assert_eq!(vec![expected_body.clone()], transport.bodies());
+
+        let transport = self::transport();
+        let misp = transport.client();
+        block_on(misp.objects().get(object).add_reference(dropped, "drops")).unwrap();
+        assert_eq!(vec!["/objectReferences/add"], paths(&transport));
+        assert_eq!(vec![expected_body], transport.bodies());
+
+        let reference = Uuid::parse_str("00000000-0000-4000-8000-000000000031").unwrap();
+        assert!(block_on(misp.objects().delete_reference(reference))
+            .unwrap()
+            .accepted());
+        assert!(block_on(misp.objects().hard_delete_reference(reference))
+            .unwrap()
+            .accepted());
+        assert_eq!(
+            vec![
+                "/objectReferences/add",
+                "/objectReferences/delete/00000000-0000-4000-8000-000000000031",
+                "/objectReferences/delete/00000000-0000-4000-8000-000000000031/1",
+            ],
+            paths(&transport)
+        );
+    }
+}
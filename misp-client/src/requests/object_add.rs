@@ -0,0 +1,39 @@
+use crate::{MispResult, MISP};
+use misp_types::event::GenericEventIdentifier;
+use misp_types::object::{NewObject, ObjectFull, ObjectFullEmbedded};
+
+/// Adds a new object, including its attributes, to an existing event.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ObjectAddRequest<'a> {
+    event: GenericEventIdentifier,
+    object: NewObject,
+    misp_client: &'a MISP,
+}
+
+impl ObjectAddRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        event: GenericEventIdentifier,
+        object: NewObject,
+    ) -> ObjectAddRequest<'_> {
+        ObjectAddRequest {
+            event,
+            object,
+            misp_client,
+        }
+    }
+
+    /// Sends the object to the server. Returns the created object, including the ids and uuids
+    /// assigned by the server.
+    pub async fn send(&self) -> MispResult<ObjectFull> {
+        let object: ObjectFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("objects/add/{}", self.event.to_url_id()),
+                &self.object,
+            )
+            .await?;
+        Ok(object.object)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{MispResult, MISP};
+use misp_types::object::{
+    GenericObjectIdentifier, ObjectFull, ObjectFullEmbedded, ObjectUpdate, ObjectUpdateEmbedded,
+};
+
+/// Edits an existing object on the server. Only the fields set in the
+/// [`ObjectUpdate`](../../misp_types/object/struct.ObjectUpdate.html) are sent.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ObjectUpdateRequest<'a> {
+    id: GenericObjectIdentifier,
+    update: ObjectUpdateEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl ObjectUpdateRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        id: GenericObjectIdentifier,
+        update: ObjectUpdate,
+    ) -> ObjectUpdateRequest<'_> {
+        ObjectUpdateRequest {
+            id,
+            update: ObjectUpdateEmbedded { object: update },
+            misp_client,
+        }
+    }
+
+    /// Sends the changes to the server. Returns the object as it is stored on the server after the
+    /// update.
+    pub async fn send(&self) -> MispResult<ObjectFull> {
+        let object: ObjectFullEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("objects/edit/{}", self.id.to_url_id()),
+                &self.update,
+            )
+            .await?;
+        Ok(object.object)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::requests::action::ActionResponse;
+use crate::{MispResult, MISP};
+use misp_types::shadow_attribute::{
+    ShadowAttribute, ShadowAttributeEmbedded, ShadowAttributeIdentifier,
+};
+
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ProposalRequest<'a> {
+    id: ShadowAttributeIdentifier,
+    misp_client: &'a MISP,
+    cached_local: Option<ShadowAttribute>,
+}
+
+impl ProposalRequest<'_> {
+    pub fn new(misp_client: &MISP, id: ShadowAttributeIdentifier) -> ProposalRequest<'_> {
+        ProposalRequest {
+            id,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<ShadowAttribute> {
+        let proposal: ShadowAttributeEmbedded = self
+            .misp_client
+            .internal_api_call_get(format!("shadow_attributes/view/{}", self.id))
+            .await?;
+        Ok(proposal.shadow_attribute)
+    }
+
+    async fn cached(&mut self) -> MispResult<&ShadowAttribute> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<ShadowAttribute> {
+        let proposal_ref = self.cached().await?;
+        Ok(proposal_ref.clone())
+    }
+
+    pub fn id(&self) -> ShadowAttributeIdentifier {
+        self.id
+    }
+
+    async fn action(&mut self, action: &str) -> MispResult<ActionResponse> {
+        let response = self
+            .misp_client
+            .internal_api_call_post(
+                format!("shadow_attributes/{}/{}", action, self.id),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.cached_local = None;
+        Ok(response)
+    }
+
+    /// Accepts the proposal: the proposed attribute, change or deletion is applied to the event.
+    /// Only the organization owning the event can accept proposals.
+    pub async fn accept(&mut self) -> MispResult<ActionResponse> {
+        self.action("accept").await
+    }
+
+    /// Discards the proposal without applying it.
+    pub async fn discard(&mut self) -> MispResult<ActionResponse> {
+        self.action("discard").await
+    }
+}
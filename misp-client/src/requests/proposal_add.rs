@@ -0,0 +1,39 @@
+use crate::{MispResult, MISP};
+use misp_types::attribute::NewAttribute;
+use misp_types::event::GenericEventIdentifier;
+use misp_types::shadow_attribute::{ShadowAttribute, ShadowAttributeEmbedded};
+
+/// Proposes a new attribute for an event of another organization.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ProposalAddRequest<'a> {
+    event: GenericEventIdentifier,
+    attribute: NewAttribute,
+    misp_client: &'a MISP,
+}
+
+impl ProposalAddRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        event: GenericEventIdentifier,
+        attribute: NewAttribute,
+    ) -> ProposalAddRequest<'_> {
+        ProposalAddRequest {
+            event,
+            attribute,
+            misp_client,
+        }
+    }
+
+    /// Sends the proposal to the server. Returns the created proposal.
+    pub async fn send(&self) -> MispResult<ShadowAttribute> {
+        let proposal: ShadowAttributeEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("shadow_attributes/add/{}", self.event.to_url_id()),
+                &self.attribute,
+            )
+            .await?;
+        Ok(proposal.shadow_attribute)
+    }
+}
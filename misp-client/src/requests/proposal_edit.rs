@@ -0,0 +1,39 @@
+use crate::{MispResult, MISP};
+use misp_types::attribute::{AttributeUpdate, AttributeUpdateEmbedded, GenericAttributeIdentifier};
+use misp_types::shadow_attribute::{ShadowAttribute, ShadowAttributeEmbedded};
+
+/// Proposes a change to an existing attribute of another organization. Only the fields set in the
+/// [`AttributeUpdate`](../../misp_types/attribute/struct.AttributeUpdate.html) are proposed.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ProposalEditRequest<'a> {
+    attribute: GenericAttributeIdentifier,
+    update: AttributeUpdateEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl ProposalEditRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        attribute: GenericAttributeIdentifier,
+        update: AttributeUpdate,
+    ) -> ProposalEditRequest<'_> {
+        ProposalEditRequest {
+            attribute,
+            update: AttributeUpdateEmbedded { attribute: update },
+            misp_client,
+        }
+    }
+
+    /// Sends the proposal to the server. Returns the created proposal.
+    pub async fn send(&self) -> MispResult<ShadowAttribute> {
+        let proposal: ShadowAttributeEmbedded = self
+            .misp_client
+            .internal_api_call_post(
+                format!("shadow_attributes/edit/{}", self.attribute.to_url_id()),
+                &self.update,
+            )
+            .await?;
+        Ok(proposal.shadow_attribute)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{MispResult, MISP};
+use misp_types::event::GenericEventIdentifier;
+use misp_types::shadow_attribute::{ShadowAttribute, ShadowAttributeEmbedded};
+
+/// Lists the pending proposals the current user can see.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct ProposalListRequest<'a> {
+    event: Option<GenericEventIdentifier>,
+    misp_client: &'a MISP,
+    cached_local: Option<Vec<ShadowAttribute>>,
+}
+
+impl ProposalListRequest<'_> {
+    pub fn new(misp_client: &MISP) -> ProposalListRequest<'_> {
+        ProposalListRequest {
+            event: None,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Vec<ShadowAttribute>> {
+        let endpoint = match &self.event {
+            Some(event) => format!("shadow_attributes/index/{}", event.to_url_id()),
+            None => "shadow_attributes/index".to_string(),
+        };
+        let proposals: Vec<ShadowAttributeEmbedded> =
+            self.misp_client.internal_api_call_get(endpoint).await?;
+        Ok(proposals.into_iter().map(|p| p.shadow_attribute).collect())
+    }
+
+    async fn cached(&mut self) -> MispResult<&Vec<ShadowAttribute>> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<Vec<ShadowAttribute>> {
+        Ok(self.cached().await?.clone())
+    }
+
+    /// Only returns the proposals for a specific event.
+    pub fn in_event(&mut self, event: impl Into<GenericEventIdentifier>) -> &mut Self {
+        self.event = Some(event.into());
+        self
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{MispResult, MISP};
+use misp_types::tag::{NewTag, NewTagEmbedded, Tag, TagEmbedded};
+
+/// Creates a new tag on the server.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct TagAddRequest<'a> {
+    tag: NewTagEmbedded,
+    misp_client: &'a MISP,
+}
+
+impl TagAddRequest<'_> {
+    pub fn new(misp_client: &MISP, tag: NewTag) -> TagAddRequest<'_> {
+        TagAddRequest {
+            tag: NewTagEmbedded { tag },
+            misp_client,
+        }
+    }
+
+    /// Sends the tag to the server. Returns the created tag, including the id assigned by the
+    /// server.
+    pub async fn send(&self) -> MispResult<Tag> {
+        let tag: TagEmbedded = self
+            .misp_client
+            .internal_api_call_post("tags/add", &self.tag)
+            .await?;
+        Ok(tag.tag)
+    }
+}
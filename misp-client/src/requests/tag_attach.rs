@@ -0,0 +1,106 @@
+use crate::requests::action::ActionResponse;
+use crate::{MispResult, MISP};
+use misp_types::tag::GenericTagIdentifier;
+use uuid::Uuid;
+
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct TagAttachment {
+    uuid: Uuid,
+    tag: GenericTagIdentifier,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    local: bool,
+}
+
+impl TagAttachment {
+    pub(crate) fn new(target: Uuid, tag: GenericTagIdentifier) -> Self {
+        Self {
+            uuid: target,
+            tag,
+            local: false,
+        }
+    }
+}
+
+/// Attaches an existing tag to an event or an attribute.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct TagAttachRequest<'a> {
+    attachment: TagAttachment,
+    misp_client: &'a MISP,
+}
+
+impl TagAttachRequest<'_> {
+    pub fn new(
+        misp_client: &MISP,
+        target: Uuid,
+        tag: GenericTagIdentifier,
+    ) -> TagAttachRequest<'_> {
+        TagAttachRequest {
+            attachment: TagAttachment::new(target, tag),
+            misp_client,
+        }
+    }
+
+    /// Attaches the tag as local tag. Local tags are only visible on this instance and are never
+    /// synchronised to other instances.
+    pub fn local(&mut self) -> &mut Self {
+        self.attachment.local = true;
+        self
+    }
+
+    pub async fn send(&self) -> MispResult<ActionResponse> {
+        self.misp_client
+            .internal_api_call_post("tags/attachTagToObject", &self.attachment)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transport::fake::{response, FakeTransport};
+    use futures::executor::block_on;
+    use misp_types::tag::TagIdentifier;
+    use uuid::Uuid;
+
+    #[test]
+    fn attach_and_detach_tags() {
+        let transport = FakeTransport::new(|_| {
+            response(200, r#"{"saved": true, "success": "Tag(s) attached."}"#)
+        });
+        let misp = transport.client();
+        let event = Uuid::parse_str("5ec4f1a0-0000-4000-8000-000000000007").unwrap();
+        assert!(block_on(misp.tags().attach(event, "tlp:amber").send())
+            .unwrap()
+            .accepted());
+        assert!(
+            block_on(misp.tags().attach(event, TagIdentifier(3)).local().send())
+                .unwrap()
+                .accepted()
+        );
+        assert!(block_on(misp.tags().detach(event, 3)).unwrap().accepted());
+
+        let paths: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url().path().to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "/tags/attachTagToObject",
+                "/tags/attachTagToObject",
+                "/tags/removeTagFromObject"
+            ],
+            paths
+        );
+        assert_eq!(
+            vec![
+                serde_json::json!({"uuid": event, "tag": "tlp:amber"}),
+                serde_json::json!({"uuid": event, "tag": "3", "local": true}),
+                serde_json::json!({"uuid": event, "tag": "3"}),
+            ],
+            transport.bodies()
+        );
+    }
+}
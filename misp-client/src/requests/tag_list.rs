@@ -0,0 +1,62 @@
+use crate::{MispResult, MISP};
+use misp_types::tag::{Tag, TagEmbedded};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+struct TagListResponse {
+    #[serde(rename = "Tag")]
+    tags: Vec<Tag>,
+}
+
+/// Lists the tags known to the server, optionally restricted to the ones matching a name.
+///
+/// The Request's lifetime is bound to the client's lifetime
+pub struct TagListRequest<'a> {
+    search: Option<String>,
+    misp_client: &'a MISP,
+    cached_local: Option<Vec<Tag>>,
+}
+
+impl TagListRequest<'_> {
+    pub fn new(misp_client: &MISP, search: Option<String>) -> TagListRequest<'_> {
+        TagListRequest {
+            search,
+            misp_client,
+            cached_local: None,
+        }
+    }
+
+    async fn download_to_cache(&mut self) -> MispResult<Vec<Tag>> {
+        match &self.search {
+            Some(name) => {
+                let tags: Vec<TagEmbedded> = self
+                    .misp_client
+                    .internal_api_call_get(format!(
+                        "tags/search/{}",
+                        utf8_percent_encode(name, NON_ALPHANUMERIC)
+                    ))
+                    .await?;
+                Ok(tags.into_iter().map(|t| t.tag).collect())
+            }
+            None => {
+                let tags: TagListResponse =
+                    self.misp_client.internal_api_call_get("tags/index").await?;
+                Ok(tags.tags)
+            }
+        }
+    }
+
+    async fn cached(&mut self) -> MispResult<&Vec<Tag>> {
+        if self.cached_local.is_none() {
+            self.cached_local = Some(self.download_to_cache().await?);
+        };
+        Ok(self.cached_local.as_ref().unwrap())
+    }
+
+    pub async fn retrieve(&mut self) -> MispResult<Vec<Tag>> {
+        let tags_ref = self.cached().await?;
+        Ok(tags_ref.clone())
+    }
+}
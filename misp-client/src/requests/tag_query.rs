@@ -0,0 +1,94 @@
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+/// A filter on the tags of events or attributes, combining tag names with AND, OR and NOT.
+///
+/// # Examples
+///
+/// Matches everything tagged with both `tlp:amber` and `ransomware`, unless it is also tagged as
+/// `false-positive`:
+/// ```
+/// # use misp_client::requests::tag_query::TagQuery;
+/// let query = TagQuery::all_of(vec!["tlp:amber", "ransomware"]).and_none_of(vec!["false-positive"]);
+/// assert_eq!(
+///     r#"{"AND":["tlp:amber","ransomware"],"NOT":["false-positive"]}"#,
+///     serde_json::to_string(&query).unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagQuery {
+    all_of: Vec<String>,
+    any_of: Vec<String>,
+    none_of: Vec<String>,
+}
+
+fn names(names: impl IntoIterator<Item = impl Into<String>>) -> impl Iterator<Item = String> {
+    names.into_iter().map(Into::into)
+}
+
+impl TagQuery {
+    /// Matches when all of the tags are attached.
+    pub fn all_of(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::default().and_all_of(tags)
+    }
+
+    /// Matches when at least one of the tags is attached.
+    pub fn any_of(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::default().and_any_of(tags)
+    }
+
+    /// Matches when none of the tags are attached.
+    pub fn none_of(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::default().and_none_of(tags)
+    }
+
+    /// Additionally requires all of the tags to be attached.
+    pub fn and_all_of(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.all_of.extend(names(tags));
+        self
+    }
+
+    /// Additionally requires at least one of the tags to be attached. The tags are added to the
+    /// existing alternatives.
+    pub fn and_any_of(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.any_of.extend(names(tags));
+        self
+    }
+
+    /// Additionally excludes the tags.
+    pub fn and_none_of(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.none_of.extend(names(tags));
+        self
+    }
+
+    /// Adds the conditions of another query to this one.
+    pub(crate) fn merge(&mut self, other: TagQuery) {
+        self.all_of.extend(other.all_of);
+        self.any_of.extend(other.any_of);
+        self.none_of.extend(other.none_of);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.all_of.is_empty() && self.any_of.is_empty() && self.none_of.is_empty()
+    }
+}
+
+impl Serialize for TagQuery {
+    /// Only the non-empty lists are sent
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for (operator, names) in &[
+            ("AND", &self.all_of),
+            ("OR", &self.any_of),
+            ("NOT", &self.none_of),
+        ] {
+            if !names.is_empty() {
+                map.serialize_entry(operator, names)?;
+            }
+        }
+        map.end()
+    }
+}
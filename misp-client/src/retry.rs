@@ -0,0 +1,219 @@
+use crate::MispError;
+use rand::Rng;
+use std::time::Duration;
+
+/// Decides if and when a failed request is sent again.
+///
+/// Requests are retried with an exponential backoff: the n-th retry waits `base_delay * 2^(n-1)`,
+/// up to `max_delay`. When the server asks to slow down (HTTP 429 with a `Retry-After` header),
+/// its delay is used instead. Requests that change data on the server (e.g. adding an event) are
+/// not retried unless [`retry_non_idempotent`](#method.retry_non_idempotent) is set, as the
+/// change may have been applied even though the response got lost.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use misp_client::{MISP, RetryPolicy};
+/// # use std::time::Duration;
+/// let mut misp = MISP::new("https://misp.demo.com", "VERYSECRETTOKEN");
+/// misp.retry_policy(
+///     RetryPolicy::new()
+///         .max_attempts(5)
+///         .base_delay(Duration::from_secs(1))
+///         .clone(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<u16>,
+    retry_transport_errors: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// The default policy: up to 3 attempts, starting with a delay of 500 milliseconds, for
+    /// connection errors and the statuses 429, 502, 503 and 504.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable_statuses: vec![429, 502, 503, 504],
+            retry_transport_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+
+    /// A policy that never retries: every request is sent exactly once.
+    pub fn never() -> Self {
+        let mut policy = Self::new();
+        policy.max_attempts(1);
+        policy
+    }
+
+    /// Sets how many times a request is sent at most, including the first attempt.
+    pub fn max_attempts(&mut self, max_attempts: u32) -> &mut Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry. The delay doubles with every retry.
+    pub fn base_delay(&mut self, base_delay: Duration) -> &mut Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the longest delay between two attempts, including delays requested by the server.
+    pub fn max_delay(&mut self, max_delay: Duration) -> &mut Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Randomizes the delays between half and all of their value, so that many clients failing
+    /// at the same time do not retry at the same time. Enabled by default.
+    pub fn jitter(&mut self, jitter: bool) -> &mut Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the HTTP statuses after which a request is retried.
+    ///
+    /// Failed authentications ([`AuthFailed`](enum.MispError.html#variant.AuthFailed), e.g. a 401)
+    /// and rejected submissions ([`ValidationFailed`](enum.MispError.html#variant.ValidationFailed),
+    /// e.g. a 403 or 422 listing the invalid fields) are never retried, even if their status is
+    /// listed: sending the same request again fails the same way.
+    pub fn retryable_statuses(&mut self, statuses: impl IntoIterator<Item = u16>) -> &mut Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Sets whether requests are retried when no response was received (e.g. the connection
+    /// was refused or timed out). Enabled by default.
+    pub fn retry_transport_errors(&mut self, retry: bool) -> &mut Self {
+        self.retry_transport_errors = retry;
+        self
+    }
+
+    /// Also retries requests that change data on the server. Disabled by default.
+    pub fn retry_non_idempotent(&mut self, retry: bool) -> &mut Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Returns true if a request that failed after `attempt` attempts is sent again.
+    pub(crate) fn should_retry(&self, error: &MispError, attempt: u32, idempotent: bool) -> bool {
+        if attempt >= self.max_attempts || !(idempotent || self.retry_non_idempotent) {
+            return false;
+        }
+        match error {
+            MispError::HttpError(_) | MispError::IoError(_) => self.retry_transport_errors,
+            MispError::RateLimited { .. } => self.retryable_statuses.contains(&429),
+            MispError::NotFound(_) => self.retryable_statuses.contains(&404),
+            MispError::Forbidden(_) => self.retryable_statuses.contains(&403),
+            MispError::UnexpectedStatus { status, .. } => self.retryable_statuses.contains(status),
+            // See `retryable_statuses`
+            MispError::AuthFailed(_) | MispError::ValidationFailed { .. } => false,
+            _ => false,
+        }
+    }
+
+    /// The delay before sending a request again after `attempt` attempts failed.
+    pub(crate) fn delay(&self, error: &MispError, attempt: u32) -> Duration {
+        if let MispError::RateLimited {
+            retry_after: Some(retry_after),
+            ..
+        } = error
+        {
+            return (*retry_after).min(self.max_delay);
+        }
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if self.jitter {
+            delay / 2 + delay.mul_f64(rand::thread_rng().gen_range(0.0, 0.5))
+        } else {
+            delay
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::error::ApiErrorResponse;
+    use crate::MispError;
+    use std::time::Duration;
+
+    fn status(status: u16) -> MispError {
+        MispError::UnexpectedStatus {
+            status,
+            response: ApiErrorResponse::default(),
+        }
+    }
+
+    #[test]
+    fn retry_decisions() {
+        let policy = RetryPolicy::new();
+        assert!(policy.should_retry(&status(502), 1, true));
+        assert!(policy.should_retry(&status(504), 2, true));
+        assert!(!policy.should_retry(&status(504), 3, true));
+        assert!(!policy.should_retry(&status(500), 1, true));
+        assert!(!policy.should_retry(&MispError::NotFound(ApiErrorResponse::default()), 1, true));
+        // Adding an event twice would create a duplicate
+        assert!(!policy.should_retry(&status(502), 1, false));
+        assert!(RetryPolicy::new()
+            .retry_non_idempotent(true)
+            .should_retry(&status(502), 1, false));
+        assert!(!RetryPolicy::never().should_retry(&status(502), 1, true));
+
+        let mut policy = RetryPolicy::new();
+        policy.retryable_statuses(vec![401, 403, 422]);
+        assert!(policy.should_retry(&status(422), 1, true));
+        assert!(policy.should_retry(&MispError::Forbidden(ApiErrorResponse::default()), 1, true));
+        let auth_failed = MispError::AuthFailed(ApiErrorResponse::default());
+        assert!(!policy.should_retry(&auth_failed, 1, true));
+        let validation_failed = MispError::ValidationFailed {
+            response: ApiErrorResponse::default(),
+            field_errors: Default::default(),
+        };
+        assert!(!policy.should_retry(&validation_failed, 1, true));
+    }
+
+    #[test]
+    fn exponential_delays() {
+        let mut policy = RetryPolicy::new();
+        policy
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(false);
+        assert_eq!(Duration::from_secs(1), policy.delay(&status(502), 1));
+        assert_eq!(Duration::from_secs(4), policy.delay(&status(502), 3));
+        assert_eq!(Duration::from_secs(5), policy.delay(&status(502), 10));
+        let rate_limited = MispError::RateLimited {
+            response: ApiErrorResponse::default(),
+            retry_after: Some(Duration::from_secs(3)),
+        };
+        assert_eq!(Duration::from_secs(3), policy.delay(&rate_limited, 1));
+
+        policy.jitter(true);
+        for attempt in 1..5 {
+            let delay = policy.delay(&status(502), attempt);
+            assert!(delay >= Duration::from_secs(2u64.pow(attempt - 1).min(5)) / 2);
+            assert!(delay <= Duration::from_secs(5));
+        }
+    }
+}
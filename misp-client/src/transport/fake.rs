@@ -0,0 +1,130 @@
+//! A transport that answers requests without a server, for the tests of the requests.
+use super::{HttpRequest, HttpResponse, HttpTransport};
+use crate::{MispResult, RetryPolicy, MISP};
+use futures::future::BoxFuture;
+use futures::io::{AsyncRead, Cursor};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+type Handler = dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync;
+
+/// Answers every request with the response of `handler` and records the requests.
+#[derive(Clone)]
+pub(crate) struct FakeTransport {
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+/// A response with a JSON (or any other) body.
+pub(crate) fn response(status: u16, body: impl Into<Vec<u8>>) -> HttpResponse {
+    HttpResponse::new(status, vec![], Cursor::new(body.into()))
+}
+
+/// A body that is read at most `chunk_size` bytes at a time, like a body received over a slow
+/// connection.
+struct ChunkedBody {
+    body: Cursor<Vec<u8>>,
+    chunk_size: usize,
+}
+
+impl AsyncRead for ChunkedBody {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let len = buf.len().min(self.chunk_size);
+        Pin::new(&mut self.body).poll_read(cx, &mut buf[..len])
+    }
+}
+
+/// A successful response whose body is delivered in chunks of `chunk_size` bytes.
+pub(crate) fn chunked_response(body: impl Into<Vec<u8>>, chunk_size: usize) -> HttpResponse {
+    let body = ChunkedBody {
+        body: Cursor::new(body.into()),
+        chunk_size,
+    };
+    HttpResponse::new(200, vec![], body)
+}
+
+/// An empty event as returned by `events/view` or `events/restSearch`, related to the events
+/// `related`.
+pub(crate) fn event(id: u64, related: &[u64]) -> serde_json::Value {
+    let org = serde_json::json!({
+        "id": "1", "name": "CIRCL", "uuid": "55f6ea5e-2c60-40e5-964f-47a8950d210f"
+    });
+    let related: Vec<serde_json::Value> = related
+        .iter()
+        .map(|id| {
+            serde_json::json!({"Event": {
+                "id": id.to_string(), "date": "2020-08-01", "threat_level_id": "1",
+                "info": format!("Event {}", id), "published": true,
+                "uuid": format!("5f2c5d1e-0000-4000-8000-{:012}", id), "analysis": "2",
+                "timestamp": "1596240000", "distribution": "1", "org_id": "1",
+                "orgc_id": "1", "Org": org
+            }})
+        })
+        .collect();
+    serde_json::json!({"Event": {
+        "id": id.to_string(), "org_id": "1", "date": "2020-08-01",
+        "info": format!("Event {}", id),
+        "uuid": format!("5f2c5d1e-0000-4000-8000-{:012}", id), "published": false,
+        "analysis": "1", "attribute_count": "0", "orgc_id": "1",
+        "timestamp": "1596240000", "distribution": "1", "sharing_group_id": "0",
+        "proposal_email_lock": false, "locked": false, "threat_level_id": "2",
+        "publish_timestamp": "0", "disable_correlation": false, "extends_uuid": "",
+        "Org": org, "Orgc": org,
+        "Attribute": [], "Object": [], "RelatedEvent": related
+    }})
+}
+
+impl FakeTransport {
+    pub(crate) fn new(
+        handler: impl Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// A client that sends its requests to this transport and never retries.
+    pub(crate) fn client(&self) -> MISP {
+        MISP::builder("https://test.xyz", "12345678")
+            .retry_policy(RetryPolicy::never())
+            .transport(self.clone())
+            .build()
+            .unwrap()
+    }
+
+    /// The requests sent so far.
+    pub(crate) fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The parsed JSON body of every POST request sent so far.
+    pub(crate) fn bodies(&self) -> Vec<serde_json::Value> {
+        self.requests()
+            .iter()
+            .filter_map(|r| r.body())
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for FakeTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakeTransport")
+            .field("requests", &self.requests)
+            .finish()
+    }
+}
+
+impl HttpTransport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, MispResult<HttpResponse>> {
+        let response = (self.handler)(&request);
+        self.requests.lock().unwrap().push(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
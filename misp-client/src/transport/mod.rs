@@ -0,0 +1,196 @@
+//! The HTTP client used to send the requests to the MISP server.
+//!
+//! [`MISP`](../struct.MISP.html) does not depend on a specific HTTP library. It hands every request
+//! to an [`HttpTransport`](trait.HttpTransport.html). Two implementations are available behind cargo
+//! features:
+//!
+//! * `surf-client` (default): [`SurfTransport`](struct.SurfTransport.html), for async-std
+//!   applications
+//! * `reqwest-client`: [`ReqwestTransport`](struct.ReqwestTransport.html), for tokio applications
+//!
+//! When both features are enabled, reqwest is used. Neither of them requires async-std, so tokio
+//! applications only need to enable `reqwest-client`. The [blocking client](../blocking/index.html)
+//! then uses [`ReqwestBlockingTransport`](struct.ReqwestBlockingTransport.html):
+//!
+//! ```toml
+//! [dependencies]
+//! misp-client = { version = "0.1", default-features = false, features = ["reqwest-client"] }
+//! ```
+//!
+//! Other HTTP libraries can be used by implementing `HttpTransport` and passing it to
+//! [`MISPBuilder::transport`](../struct.MISPBuilder.html#method.transport).
+use crate::MispResult;
+use futures::future::BoxFuture;
+use futures::io::AsyncRead;
+use url::Url;
+
+#[cfg(test)]
+pub(crate) mod fake;
+#[cfg(feature = "reqwest-client")]
+mod reqwest_transport;
+#[cfg(feature = "surf-client")]
+mod surf_transport;
+
+#[cfg(all(feature = "reqwest-client", feature = "blocking"))]
+pub use reqwest_transport::ReqwestBlockingTransport;
+#[cfg(feature = "reqwest-client")]
+pub use reqwest_transport::ReqwestTransport;
+#[cfg(feature = "surf-client")]
+pub use surf_transport::SurfTransport;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// A request to the MISP server, with all headers already set.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    method: Method,
+    url: Url,
+    headers: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub(crate) fn new(
+        method: Method,
+        url: Url,
+        headers: Vec<(String, String)>,
+        body: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            method,
+            url,
+            headers,
+            body,
+        }
+    }
+
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The JSON body of POST requests.
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+}
+
+/// The response of the MISP server. The body is read while it is received.
+pub struct HttpResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Box<dyn AsyncRead + Send + Unpin>,
+}
+
+impl HttpResponse {
+    pub fn new(
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: impl AsyncRead + Send + Unpin + 'static,
+    ) -> Self {
+        Self {
+            status,
+            headers,
+            body: Box::new(body),
+        }
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the first value of a header. Header names are case insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn into_body(self) -> Box<dyn AsyncRead + Send + Unpin> {
+        self.body
+    }
+}
+
+impl std::fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// Sends requests over HTTP.
+///
+/// Implementations only transfer the requests: unsuccessful statuses are handled and retries are
+/// done by the client. Errors that prevented receiving a response (e.g. a refused connection)
+/// are reported as [`MispError::HttpError`](../enum.MispError.html#variant.HttpError).
+pub trait HttpTransport: std::fmt::Debug + Send + Sync + 'static {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, MispResult<HttpResponse>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::{response, FakeTransport};
+    use super::{HttpResponse, Method};
+    use crate::{MispError, RetryPolicy, MISP};
+    use futures::executor::block_on;
+    use futures::io::Cursor;
+
+    #[test]
+    fn custom_transport() {
+        let transport = FakeTransport::new(|request| match request.url().path() {
+            "/servers/getVersion.json" => response(
+                200,
+                r#"{"version": "2.4.130", "perm_sync": false, "perm_sighting": true}"#,
+            ),
+            _ => HttpResponse::new(
+                429,
+                vec![("retry-after".into(), "10".into())],
+                Cursor::new(b"Slow down".to_vec()),
+            ),
+        });
+        let misp = MISP::builder("https://test.xyz", "12345678")
+            .user_agent("tests")
+            .retry_policy(RetryPolicy::never())
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let info = block_on(misp.server_info()).unwrap();
+        assert_eq!("2.4.130", info.version);
+        let requests = transport.requests();
+        assert_eq!(Method::Get, requests[0].method());
+        assert!(requests[0]
+            .headers()
+            .contains(&("Authorization".into(), "12345678".into())));
+        assert!(requests[0]
+            .headers()
+            .contains(&("User-Agent".into(), "tests".into())));
+
+        let error =
+            block_on(misp.events().list().from_organization("CIRCL").retrieve()).unwrap_err();
+        assert!(matches!(
+            error,
+            MispError::RateLimited {
+                retry_after: Some(_),
+                ..
+            }
+        ));
+        let requests = transport.requests();
+        assert_eq!(Method::Post, requests[1].method());
+        assert!(requests[1].body().is_some());
+    }
+}
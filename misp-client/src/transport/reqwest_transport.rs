@@ -0,0 +1,160 @@
+use super::{HttpRequest, HttpResponse, HttpTransport, Method};
+use crate::builder::MISPBuilder;
+use crate::error::{MispError, MispResult};
+use futures::future::BoxFuture;
+#[cfg(feature = "blocking")]
+use futures::io::AllowStdIo;
+use futures::TryStreamExt;
+
+/// Sends the requests with [reqwest](https://docs.rs/reqwest). Requires the `reqwest-client`
+/// feature. The requests must be awaited inside of a tokio runtime.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+fn configuration_error(error: impl std::fmt::Display) -> MispError {
+    MispError::ConfigurationError(error.to_string())
+}
+
+/// Applies the TLS, proxy and timeout settings of a `MISPBuilder` to a reqwest client builder,
+/// async or blocking.
+macro_rules! configure {
+    ($reqwest_builder:expr, $builder:expr) => {{
+        let builder: &MISPBuilder = $builder;
+        let mut reqwest_builder = $reqwest_builder;
+        if let Some(timeout) = builder.timeout {
+            reqwest_builder = reqwest_builder.timeout(timeout);
+        }
+        if let Some(timeout) = builder.connect_timeout {
+            reqwest_builder = reqwest_builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &builder.proxy {
+            reqwest_builder =
+                reqwest_builder.proxy(reqwest::Proxy::all(proxy).map_err(configuration_error)?);
+        }
+        if let Some(path) = &builder.ca_certificate {
+            let pem = std::fs::read(path).map_err(configuration_error)?;
+            reqwest_builder = reqwest_builder.add_root_certificate(
+                reqwest::Certificate::from_pem(&pem).map_err(configuration_error)?,
+            );
+        }
+        if let Some((certificate, private_key)) = &builder.client_certificate {
+            // rustls expects the key and the certificate chain in a single PEM document
+            let mut pem = std::fs::read(private_key).map_err(configuration_error)?;
+            pem.push(b'\n');
+            pem.extend(std::fs::read(certificate).map_err(configuration_error)?);
+            reqwest_builder = reqwest_builder
+                .identity(reqwest::Identity::from_pem(&pem).map_err(configuration_error)?);
+        }
+        if builder.accept_invalid_certificates {
+            reqwest_builder = reqwest_builder.danger_accept_invalid_certs(true);
+        }
+        reqwest_builder
+    }};
+}
+
+impl ReqwestTransport {
+    /// Uses an already configured client. The TLS, proxy and timeout settings of the
+    /// [`MISPBuilder`](../struct.MISPBuilder.html) are ignored.
+    pub fn from_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+
+    pub(crate) fn from_builder(builder: &MISPBuilder) -> MispResult<Self> {
+        let client = configure!(reqwest::Client::builder(), builder)
+            .build()
+            .map_err(configuration_error)?;
+        Ok(Self { client })
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, MispResult<HttpResponse>> {
+        Box::pin(async move {
+            let method = match request.method() {
+                Method::Get => reqwest::Method::GET,
+                Method::Post => reqwest::Method::POST,
+            };
+            let mut builder = self.client.request(method, request.url().clone());
+            for (name, value) in request.headers() {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = request.body() {
+                builder = builder.body(body.to_vec());
+            }
+            let response = builder.send().await?;
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            let status = response.status().as_u16();
+            let body =
+                Box::pin(response.bytes_stream().map_err(std::io::Error::other)).into_async_read();
+            Ok(HttpResponse::new(status, headers, body))
+        })
+    }
+}
+
+/// Sends the requests with the blocking client of [reqwest](https://docs.rs/reqwest). Requires
+/// the `reqwest-client` and `blocking` features. It is used by the
+/// [blocking client](../blocking/index.html) and must not be used from within async code.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Clone)]
+pub struct ReqwestBlockingTransport {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "blocking")]
+impl ReqwestBlockingTransport {
+    /// Uses an already configured client. The TLS, proxy and timeout settings of the
+    /// [`MISPBuilder`](../struct.MISPBuilder.html) are ignored.
+    pub fn from_client(client: reqwest::blocking::Client) -> Self {
+        Self { client }
+    }
+
+    pub(crate) fn from_builder(builder: &MISPBuilder) -> MispResult<Self> {
+        let client = configure!(reqwest::blocking::Client::builder(), builder)
+            .build()
+            .map_err(configuration_error)?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl HttpTransport for ReqwestBlockingTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, MispResult<HttpResponse>> {
+        // The request blocks the thread polling the future, which is the calling thread of the
+        // blocking client
+        Box::pin(async move {
+            let method = match request.method() {
+                Method::Get => reqwest::Method::GET,
+                Method::Post => reqwest::Method::POST,
+            };
+            let mut builder = self.client.request(method, request.url().clone());
+            for (name, value) in request.headers() {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = request.body() {
+                builder = builder.body(body.to_vec());
+            }
+            let response = builder.send()?;
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            let status = response.status().as_u16();
+            Ok(HttpResponse::new(
+                status,
+                headers,
+                AllowStdIo::new(response),
+            ))
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use super::{HttpRequest, HttpResponse, HttpTransport, Method};
+use crate::builder::MISPBuilder;
+use crate::error::{MispError, MispResult};
+use futures::future::BoxFuture;
+use http_client::isahc::IsahcClient;
+use isahc::config::{CaCertificate, ClientCertificate, Configurable, PrivateKey, SslOption};
+use std::convert::TryInto;
+use surf::http::headers::HeaderName;
+
+/// Sends the requests with [surf](https://docs.rs/surf) and its curl backend. Requires the
+/// `surf-client` feature.
+#[derive(Debug, Clone)]
+pub struct SurfTransport {
+    client: surf::Client,
+}
+
+impl SurfTransport {
+    /// Uses an already configured client. The TLS, proxy and timeout settings of the
+    /// [`MISPBuilder`](../struct.MISPBuilder.html) are ignored.
+    pub fn from_client(client: surf::Client) -> Self {
+        Self { client }
+    }
+
+    pub(crate) fn from_builder(builder: &MISPBuilder) -> MispResult<Self> {
+        let mut isahc_builder = isahc::HttpClient::builder();
+        if let Some(timeout) = builder.timeout {
+            isahc_builder = isahc_builder.timeout(timeout);
+        }
+        if let Some(timeout) = builder.connect_timeout {
+            isahc_builder = isahc_builder.connect_timeout(timeout);
+        }
+        if let Some(proxy) = &builder.proxy {
+            let proxy: isahc::http::Uri = proxy
+                .parse()
+                .map_err(|e| MispError::ConfigurationError(format!("Invalid proxy: {}", e)))?;
+            isahc_builder = isahc_builder.proxy(Some(proxy));
+        }
+        if let Some(path) = &builder.ca_certificate {
+            isahc_builder = isahc_builder.ssl_ca_certificate(CaCertificate::file(path));
+        }
+        if let Some((certificate, private_key)) = &builder.client_certificate {
+            isahc_builder = isahc_builder.ssl_client_certificate(ClientCertificate::pem_file(
+                certificate,
+                PrivateKey::pem_file(private_key, None),
+            ));
+        }
+        if builder.accept_invalid_certificates {
+            isahc_builder = isahc_builder.ssl_options(
+                SslOption::DANGER_ACCEPT_INVALID_CERTS | SslOption::DANGER_ACCEPT_INVALID_HOSTS,
+            );
+        }
+        let isahc_client = isahc_builder
+            .build()
+            .map_err(|e| MispError::ConfigurationError(e.to_string()))?;
+        let client = surf::Config::new()
+            .set_http_client(IsahcClient::from_client(isahc_client))
+            .try_into()
+            .map_err(|e: <surf::Config as TryInto<surf::Client>>::Error| {
+                MispError::ConfigurationError(e.to_string())
+            })?;
+        Ok(Self { client })
+    }
+}
+
+impl HttpTransport for SurfTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, MispResult<HttpResponse>> {
+        Box::pin(async move {
+            let method = match request.method() {
+                Method::Get => surf::http::Method::Get,
+                Method::Post => surf::http::Method::Post,
+            };
+            let mut builder = self.client.request(method, request.url());
+            for (name, value) in request.headers() {
+                let name: HeaderName = name.parse()?;
+                builder = builder.header(name, value.as_str());
+            }
+            if let Some(body) = request.body() {
+                builder = builder.body(body.to_vec());
+            }
+            let response = builder.await?;
+            let headers = response
+                .iter()
+                .flat_map(|(name, values)| {
+                    values
+                        .iter()
+                        .map(move |value| (name.to_string(), value.to_string()))
+                })
+                .collect();
+            Ok(HttpResponse::new(
+                u16::from(response.status()),
+                headers,
+                response,
+            ))
+        })
+    }
+}
@@ -1,21 +1,27 @@
+use crate::attribute_type::{AttributeCategory, AttributeType};
+use crate::attribute_value::{AttributeValue, AttributeValueError};
+use crate::distribution::Distribution;
 use crate::event::EventIdentifier;
+use crate::galaxy::{Galaxy, GalaxyCluster};
 use crate::object::ObjectIdentifier;
+use crate::shadow_attribute::ShadowAttribute;
+use crate::tag::{find_tag, Tag};
+use crate::validation::{normalize, ValidationError};
 use chrono::{DateTime, Utc};
 use core::fmt;
 use uuid::Uuid;
 
 #[cfg(feature = "serde")]
 use super::serialization_helpers::{
-    datetime_to_epoch, number_embedded_in_string, option_datetime_to_epoch,
+    datetime_to_epoch, number_embedded_in_string, option_datetime_to_epoch, tag_names,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-#[cfg(feature = "serde")]
-use serde_json::Value;
 
 #[derive(Debug, Copy, Clone)]
 pub struct AttributeIdentifier(pub u64);
 
+#[derive(Debug, Clone)]
 pub enum GenericAttributeIdentifier {
     Global(Uuid),
     Local(AttributeIdentifier),
@@ -28,9 +34,9 @@ pub struct Attribute {
     event_id: EventIdentifier,
     object_id: ObjectIdentifier, // TODO Make it an Option?
     object_relation: Option<String>,
-    category: String,
+    category: AttributeCategory,
     #[serde(rename = "type")]
-    kind: String,
+    kind: AttributeType,
     //value1: String,
     //value2: String,
     value: String,
@@ -54,15 +60,84 @@ pub struct AttributeFull {
     attribute: Attribute,
 
     #[serde(rename = "Galaxy")]
-    galaxies: Value,
+    #[serde(default)]
+    galaxies: Vec<Galaxy>,
     #[serde(rename = "ShadowAttribute")]
-    shadow_attributes: Value,
+    #[serde(default)]
+    shadow_attributes: Vec<ShadowAttribute>,
+    #[serde(rename = "Tag")]
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default)]
     #[serde(with = "option_datetime_to_epoch")]
     first_seen: Option<DateTime<Utc>>,
+    #[serde(default)]
     #[serde(with = "option_datetime_to_epoch")]
     last_seen: Option<DateTime<Utc>>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AttributeFullEmbedded {
+    #[serde(rename = "Attribute")]
+    pub attribute: AttributeFull,
+}
+
+/// An attribute that only exists locally and has not been sent to a MISP server yet.
+///
+/// Server-assigned fields (id, event_id, timestamp, ...) are missing. Every optional field that is
+/// not set will be left out of the JSON, so that the server falls back to its defaults.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewAttribute {
+    #[serde(rename = "type")]
+    kind: AttributeType,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<AttributeCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_relation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_ids: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_correlation: Option<bool>,
+    #[serde(rename = "Tag")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(with = "tag_names")]
+    tags: Vec<String>,
+}
+
+/// Partial changes to an existing attribute. Only the fields that were set are sent to the server,
+/// every other field is left untouched.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AttributeUpdate {
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<AttributeType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<AttributeCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to_ids: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_correlation: Option<bool>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AttributeUpdateEmbedded {
+    #[serde(rename = "Attribute")]
+    pub attribute: AttributeUpdate,
+}
+
 impl Serialize for AttributeIdentifier {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -87,21 +162,67 @@ impl fmt::Display for AttributeIdentifier {
     }
 }
 
+impl GenericAttributeIdentifier {
+    pub fn to_url_id(&self) -> String {
+        match self {
+            GenericAttributeIdentifier::Global(uuid) => uuid
+                .to_hyphenated()
+                .encode_lower(&mut Uuid::encode_buffer())
+                .to_string(),
+            GenericAttributeIdentifier::Local(v) => v.to_string(),
+        }
+    }
+}
+
+impl Into<GenericAttributeIdentifier> for u64 {
+    fn into(self) -> GenericAttributeIdentifier {
+        GenericAttributeIdentifier::Local(AttributeIdentifier(self))
+    }
+}
+
+impl Into<GenericAttributeIdentifier> for Uuid {
+    fn into(self) -> GenericAttributeIdentifier {
+        GenericAttributeIdentifier::Global(self)
+    }
+}
+
+impl Into<GenericAttributeIdentifier> for Attribute {
+    fn into(self) -> GenericAttributeIdentifier {
+        GenericAttributeIdentifier::Global(self.uuid)
+    }
+}
+
+impl Into<GenericAttributeIdentifier> for AttributeFull {
+    fn into(self) -> GenericAttributeIdentifier {
+        GenericAttributeIdentifier::Global(self.attribute.uuid)
+    }
+}
+
+impl Into<GenericAttributeIdentifier> for AttributeFullEmbedded {
+    fn into(self) -> GenericAttributeIdentifier {
+        GenericAttributeIdentifier::Global(self.attribute.attribute.uuid)
+    }
+}
+
 impl Attribute {
     pub fn id(&self) -> AttributeIdentifier {
         self.id
     }
 
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
     // Returns the organization that is currently handling the event
     pub fn event_identifer(&self) -> EventIdentifier {
         self.event_id
     }
 
-    pub fn category(&self) -> &str {
+    pub fn category(&self) -> &AttributeCategory {
         &self.category
     }
 
-    pub fn kind(&self) -> &str {
+    pub fn kind(&self) -> &AttributeType {
         &self.kind
     }
 
@@ -109,23 +230,247 @@ impl Attribute {
         &self.value
     }
 
+    /// Parses the value according to the type of the attribute (e.g. an `ip-dst` into an IP
+    /// address or a network).
+    pub fn value_parsed(&self) -> Result<AttributeValue, AttributeValueError> {
+        AttributeValue::parse(&self.kind, &self.value)
+    }
+
     pub fn object_relation(&self) -> Option<&str> {
         self.object_relation.as_ref().map(String::as_str)
     }
+
+    /// Returns true if the attribute can be used for automatic detection (IDS flag).
+    pub fn to_ids(&self) -> bool {
+        self.to_ids
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Returns true if the attribute was soft deleted.
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn disable_correlation(&self) -> bool {
+        self.disable_correlation
+    }
+}
+
+impl NewAttribute {
+    /// Creates a new local attribute given its MISP type (e.g. `AttributeType::IpDst`) and its
+    /// value. Type names can be parsed with `"ip-dst".parse()`, which rejects unknown names.
+    pub fn new(kind: AttributeType, value: impl Into<String>) -> Self {
+        Self {
+            kind,
+            value: value.into(),
+            category: None,
+            object_relation: None,
+            uuid: None,
+            to_ids: None,
+            distribution: None,
+            comment: None,
+            disable_correlation: None,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> &AttributeType {
+        &self.kind
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn object_relation(&self) -> Option<&str> {
+        self.object_relation.as_deref()
+    }
+
+    /// Normalises the value the way the server does when it saves the attribute (see
+    /// [`validation::normalize`](../validation/fn.normalize.html)). Fails if the value is not
+    /// valid for the type of the attribute.
+    pub fn normalize(&mut self) -> Result<&mut Self, ValidationError> {
+        self.value = normalize(&self.kind, &self.value)?;
+        Ok(self)
+    }
+
+    /// Sets the category. If not set, the server picks the default category of the type (see
+    /// [`AttributeType::default_category`](../attribute_type/enum.AttributeType.html#method.default_category)).
+    pub fn category(&mut self, category: AttributeCategory) -> &mut Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Sets the relation of this attribute inside of an object (e.g. `filename` in a `file`
+    /// object).
+    pub fn relation(&mut self, object_relation: impl Into<String>) -> &mut Self {
+        self.object_relation = Some(object_relation.into());
+        self
+    }
+
+    /// Uses a specific uuid instead of letting the server generate one.
+    pub fn uuid(&mut self, uuid: Uuid) -> &mut Self {
+        self.uuid = Some(uuid);
+        self
+    }
+
+    /// Marks the attribute as usable for automatic detection (IDS flag).
+    pub fn to_ids(&mut self, to_ids: bool) -> &mut Self {
+        self.to_ids = Some(to_ids);
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn disable_correlation(&mut self, disable_correlation: bool) -> &mut Self {
+        self.disable_correlation = Some(disable_correlation);
+        self
+    }
+
+    /// Attaches a tag to the attribute. The tag is referenced by its name.
+    pub fn add_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.tags.push(name.into());
+        self
+    }
+}
+
+impl AttributeUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn kind(&mut self, kind: AttributeType) -> &mut Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn category(&mut self, category: AttributeCategory) -> &mut Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn to_ids(&mut self, to_ids: bool) -> &mut Self {
+        self.to_ids = Some(to_ids);
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn disable_correlation(&mut self, disable_correlation: bool) -> &mut Self {
+        self.disable_correlation = Some(disable_correlation);
+        self
+    }
 }
 
 impl AttributeFull {
-    pub fn category(&self) -> &str {
+    pub fn galaxies(&self) -> &Vec<Galaxy> {
+        &self.galaxies
+    }
+
+    /// Returns the galaxy clusters attached to the attribute, across all galaxies.
+    pub fn galaxy_clusters(&self) -> impl Iterator<Item = &GalaxyCluster> {
+        self.galaxies.iter().flat_map(|g| g.clusters().iter())
+    }
+
+    /// The pending proposals (changes or deletion) for the attribute.
+    pub fn proposals(&self) -> &Vec<ShadowAttribute> {
+        &self.shadow_attributes
+    }
+
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    /// Returns true if a tag with the given name (e.g. `tlp:red`) is attached to the attribute.
+    pub fn has_tag(&self, name: impl AsRef<str>) -> bool {
+        self.tag(name).is_some()
+    }
+
+    /// Finds a tag attached to the attribute by its name.
+    pub fn tag(&self, name: impl AsRef<str>) -> Option<&Tag> {
+        find_tag(&self.tags, name.as_ref())
+    }
+
+    //
+    // The following functions are copied from Attribute. They just call the embedded attribute
+    // methods.
+    //
+
+    pub fn id(&self) -> AttributeIdentifier {
+        self.attribute.id()
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.attribute.uuid()
+    }
+
+    pub fn event_identifer(&self) -> EventIdentifier {
+        self.attribute.event_identifer()
+    }
+
+    pub fn to_ids(&self) -> bool {
+        self.attribute.to_ids()
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        self.attribute.timestamp()
+    }
+
+    pub fn comment(&self) -> &str {
+        self.attribute.comment()
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.attribute.deleted()
+    }
+
+    pub fn disable_correlation(&self) -> bool {
+        self.attribute.disable_correlation()
+    }
+
+    pub fn category(&self) -> &AttributeCategory {
         self.attribute.category()
     }
 
-    pub fn kind(&self) -> &str {
+    pub fn kind(&self) -> &AttributeType {
         self.attribute.kind()
     }
     pub fn value(&self) -> &str {
         self.attribute.value()
     }
 
+    pub fn value_parsed(&self) -> Result<AttributeValue, AttributeValueError> {
+        self.attribute.value_parsed()
+    }
+
     pub fn object_relation(&self) -> Option<&str> {
         self.attribute.object_relation()
     }
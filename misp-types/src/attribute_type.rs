@@ -0,0 +1,665 @@
+//! The types and categories of attributes, as listed by the `attributes/describeTypes` endpoint
+//! of MISP.
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! attribute_types {
+    ($($variant:ident => $name:literal, $category:ident;)*) => {
+        /// The type of an attribute (e.g. `ip-dst` or `filename|sha256`). Types that are unknown to
+        /// this library (e.g. added by a newer MISP version) are kept as `Other`.
+        ///
+        /// Note that `OtherType` is the MISP type called `other`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum AttributeType {
+            $(
+                #[doc = $name]
+                $variant,
+            )*
+            Other(String),
+        }
+
+        impl AttributeType {
+            /// All the types known to this library.
+            pub const ALL: &'static [AttributeType] = &[$(AttributeType::$variant),*];
+
+            /// Returns the name of the type as used by MISP.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(AttributeType::$variant => $name,)*
+                    AttributeType::Other(name) => name,
+                }
+            }
+
+            /// Returns the category MISP assigns to an attribute of this type if none is given.
+            /// Unknown types have no default category.
+            pub fn default_category(&self) -> Option<AttributeCategory> {
+                match self {
+                    $(AttributeType::$variant => Some(AttributeCategory::$category),)*
+                    AttributeType::Other(_) => None,
+                }
+            }
+
+            fn known(name: &str) -> Option<AttributeType> {
+                match name {
+                    $($name => Some(AttributeType::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! attribute_categories {
+    ($($variant:ident => $name:literal, [$($kind:literal),* $(,)?];)*) => {
+        /// The category of an attribute (e.g. `Network activity`). Categories that are unknown to
+        /// this library are kept as `Other`.
+        ///
+        /// Note that `OtherCategory` is the MISP category called `Other`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum AttributeCategory {
+            $(
+                #[doc = $name]
+                $variant,
+            )*
+            Other(String),
+        }
+
+        impl AttributeCategory {
+            /// All the categories known to this library.
+            pub const ALL: &'static [AttributeCategory] = &[$(AttributeCategory::$variant),*];
+
+            /// Returns the name of the category as used by MISP.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(AttributeCategory::$variant => $name,)*
+                    AttributeCategory::Other(name) => name,
+                }
+            }
+
+            fn type_names(&self) -> &'static [&'static str] {
+                match self {
+                    $(AttributeCategory::$variant => &[$($kind),*],)*
+                    AttributeCategory::Other(_) => &[],
+                }
+            }
+
+            fn known(name: &str) -> Option<AttributeCategory> {
+                match name {
+                    $($name => Some(AttributeCategory::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+attribute_types! {
+    Md5 => "md5", PayloadDelivery;
+    Sha1 => "sha1", PayloadDelivery;
+    Sha256 => "sha256", PayloadDelivery;
+    Filename => "filename", PayloadDelivery;
+    Pdb => "pdb", ArtifactsDropped;
+    FilenameMd5 => "filename|md5", PayloadDelivery;
+    FilenameSha1 => "filename|sha1", PayloadDelivery;
+    FilenameSha256 => "filename|sha256", PayloadDelivery;
+    IpSrc => "ip-src", NetworkActivity;
+    IpDst => "ip-dst", NetworkActivity;
+    Hostname => "hostname", NetworkActivity;
+    Domain => "domain", NetworkActivity;
+    DomainIp => "domain|ip", NetworkActivity;
+    Email => "email", SocialNetwork;
+    EmailSrc => "email-src", PayloadDelivery;
+    Eppn => "eppn", NetworkActivity;
+    EmailDst => "email-dst", NetworkActivity;
+    EmailSubject => "email-subject", PayloadDelivery;
+    EmailAttachment => "email-attachment", PayloadDelivery;
+    EmailBody => "email-body", PayloadDelivery;
+    Float => "float", OtherCategory;
+    GitCommitId => "git-commit-id", InternalReference;
+    Url => "url", NetworkActivity;
+    HttpMethod => "http-method", NetworkActivity;
+    UserAgent => "user-agent", NetworkActivity;
+    Ja3FingerprintMd5 => "ja3-fingerprint-md5", NetworkActivity;
+    JarmFingerprint => "jarm-fingerprint", NetworkActivity;
+    FaviconMmh3 => "favicon-mmh3", NetworkActivity;
+    HasshMd5 => "hassh-md5", NetworkActivity;
+    HasshserverMd5 => "hasshserver-md5", NetworkActivity;
+    Regkey => "regkey", PersistenceMechanism;
+    RegkeyValue => "regkey|value", PersistenceMechanism;
+    As => "AS", NetworkActivity;
+    Snort => "snort", NetworkActivity;
+    Bro => "bro", NetworkActivity;
+    Zeek => "zeek", NetworkActivity;
+    CommunityId => "community-id", NetworkActivity;
+    PatternInFile => "pattern-in-file", PayloadInstallation;
+    PatternInTraffic => "pattern-in-traffic", NetworkActivity;
+    PatternInMemory => "pattern-in-memory", PayloadInstallation;
+    FilenamePattern => "filename-pattern", PayloadInstallation;
+    PgpPublicKey => "pgp-public-key", Person;
+    PgpPrivateKey => "pgp-private-key", Person;
+    SshFingerprint => "ssh-fingerprint", NetworkActivity;
+    Yara => "yara", PayloadInstallation;
+    Stix2Pattern => "stix2-pattern", PayloadInstallation;
+    Sigma => "sigma", PayloadInstallation;
+    Gene => "gene", ArtifactsDropped;
+    KustoQuery => "kusto-query", ArtifactsDropped;
+    MimeType => "mime-type", ArtifactsDropped;
+    IdentityCardNumber => "identity-card-number", Person;
+    Cookie => "cookie", NetworkActivity;
+    Vulnerability => "vulnerability", ExternalAnalysis;
+    Cpe => "cpe", OtherCategory;
+    Weakness => "weakness", ExternalAnalysis;
+    Attachment => "attachment", ExternalAnalysis;
+    MalwareSample => "malware-sample", PayloadDelivery;
+    Link => "link", ExternalAnalysis;
+    Comment => "comment", OtherCategory;
+    Text => "text", OtherCategory;
+    Hex => "hex", OtherCategory;
+    OtherType => "other", OtherCategory;
+    NamedPipe => "named pipe", ArtifactsDropped;
+    Mutex => "mutex", ArtifactsDropped;
+    ProcessState => "process-state", ArtifactsDropped;
+    TargetUser => "target-user", TargetingData;
+    TargetEmail => "target-email", TargetingData;
+    TargetMachine => "target-machine", TargetingData;
+    TargetOrg => "target-org", TargetingData;
+    TargetLocation => "target-location", TargetingData;
+    TargetExternal => "target-external", TargetingData;
+    Btc => "btc", FinancialFraud;
+    Dash => "dash", FinancialFraud;
+    Xmr => "xmr", FinancialFraud;
+    Iban => "iban", FinancialFraud;
+    Bic => "bic", FinancialFraud;
+    BankAccountNr => "bank-account-nr", FinancialFraud;
+    AbaRtn => "aba-rtn", FinancialFraud;
+    Bin => "bin", FinancialFraud;
+    CcNumber => "cc-number", FinancialFraud;
+    Prtn => "prtn", FinancialFraud;
+    PhoneNumber => "phone-number", Person;
+    ThreatActor => "threat-actor", Attribution;
+    CampaignName => "campaign-name", Attribution;
+    CampaignId => "campaign-id", Attribution;
+    MalwareType => "malware-type", PayloadDelivery;
+    Uri => "uri", NetworkActivity;
+    Authentihash => "authentihash", PayloadDelivery;
+    Vhash => "vhash", PayloadDelivery;
+    Ssdeep => "ssdeep", PayloadDelivery;
+    Imphash => "imphash", PayloadDelivery;
+    Telfhash => "telfhash", PayloadDelivery;
+    Pehash => "pehash", PayloadDelivery;
+    Impfuzzy => "impfuzzy", PayloadDelivery;
+    Sha224 => "sha224", PayloadDelivery;
+    Sha384 => "sha384", PayloadDelivery;
+    Sha512 => "sha512", PayloadDelivery;
+    Sha512_224 => "sha512/224", PayloadDelivery;
+    Sha512_256 => "sha512/256", PayloadDelivery;
+    Sha3_224 => "sha3-224", PayloadDelivery;
+    Sha3_256 => "sha3-256", PayloadDelivery;
+    Sha3_384 => "sha3-384", PayloadDelivery;
+    Sha3_512 => "sha3-512", PayloadDelivery;
+    Tlsh => "tlsh", PayloadDelivery;
+    Cdhash => "cdhash", PayloadDelivery;
+    FilenameSha224 => "filename|sha224", PayloadDelivery;
+    FilenameSha384 => "filename|sha384", PayloadDelivery;
+    FilenameSha512 => "filename|sha512", PayloadDelivery;
+    FilenameSha512_224 => "filename|sha512/224", PayloadDelivery;
+    FilenameSha512_256 => "filename|sha512/256", PayloadDelivery;
+    FilenameSha3_224 => "filename|sha3-224", PayloadDelivery;
+    FilenameSha3_256 => "filename|sha3-256", PayloadDelivery;
+    FilenameSha3_384 => "filename|sha3-384", PayloadDelivery;
+    FilenameSha3_512 => "filename|sha3-512", PayloadDelivery;
+    FilenameAuthentihash => "filename|authentihash", PayloadDelivery;
+    FilenameVhash => "filename|vhash", PayloadDelivery;
+    FilenameSsdeep => "filename|ssdeep", PayloadDelivery;
+    FilenameImphash => "filename|imphash", PayloadDelivery;
+    FilenameImpfuzzy => "filename|impfuzzy", PayloadDelivery;
+    FilenamePehash => "filename|pehash", PayloadDelivery;
+    FilenameTlsh => "filename|tlsh", PayloadDelivery;
+    WindowsScheduledTask => "windows-scheduled-task", ArtifactsDropped;
+    WindowsServiceName => "windows-service-name", ArtifactsDropped;
+    WindowsServiceDisplayname => "windows-service-displayname", ArtifactsDropped;
+    WhoisRegistrantEmail => "whois-registrant-email", Attribution;
+    WhoisRegistrantPhone => "whois-registrant-phone", Attribution;
+    WhoisRegistrantName => "whois-registrant-name", Attribution;
+    WhoisRegistrantOrg => "whois-registrant-org", Attribution;
+    WhoisRegistrar => "whois-registrar", Attribution;
+    WhoisCreationDate => "whois-creation-date", Attribution;
+    X509FingerprintSha1 => "x509-fingerprint-sha1", NetworkActivity;
+    X509FingerprintMd5 => "x509-fingerprint-md5", NetworkActivity;
+    X509FingerprintSha256 => "x509-fingerprint-sha256", NetworkActivity;
+    DnsSoaEmail => "dns-soa-email", Attribution;
+    SizeInBytes => "size-in-bytes", OtherCategory;
+    Counter => "counter", OtherCategory;
+    Datetime => "datetime", OtherCategory;
+    Port => "port", NetworkActivity;
+    IpDstPort => "ip-dst|port", NetworkActivity;
+    IpSrcPort => "ip-src|port", NetworkActivity;
+    HostnamePort => "hostname|port", NetworkActivity;
+    MacAddress => "mac-address", NetworkActivity;
+    MacEui64 => "mac-eui-64", NetworkActivity;
+    EmailDstDisplayName => "email-dst-display-name", PayloadDelivery;
+    EmailSrcDisplayName => "email-src-display-name", PayloadDelivery;
+    EmailHeader => "email-header", PayloadDelivery;
+    EmailReplyTo => "email-reply-to", PayloadDelivery;
+    EmailXMailer => "email-x-mailer", PayloadDelivery;
+    EmailMimeBoundary => "email-mime-boundary", PayloadDelivery;
+    EmailThreadIndex => "email-thread-index", PayloadDelivery;
+    EmailMessageId => "email-message-id", PayloadDelivery;
+    GithubUsername => "github-username", SocialNetwork;
+    GithubRepository => "github-repository", SocialNetwork;
+    GithubOrganisation => "github-organisation", SocialNetwork;
+    JabberId => "jabber-id", SocialNetwork;
+    TwitterId => "twitter-id", SocialNetwork;
+    Dkim => "dkim", NetworkActivity;
+    DkimSignature => "dkim-signature", NetworkActivity;
+    FirstName => "first-name", Person;
+    MiddleName => "middle-name", Person;
+    LastName => "last-name", Person;
+    FullName => "full-name", Person;
+    DateOfBirth => "date-of-birth", Person;
+    PlaceOfBirth => "place-of-birth", Person;
+    Gender => "gender", Person;
+    PassportNumber => "passport-number", Person;
+    PassportCountry => "passport-country", Person;
+    PassportExpiration => "passport-expiration", Person;
+    RedressNumber => "redress-number", Person;
+    Nationality => "nationality", Person;
+    VisaNumber => "visa-number", Person;
+    IssueDateOfTheVisa => "issue-date-of-the-visa", Person;
+    PrimaryResidence => "primary-residence", Person;
+    CountryOfResidence => "country-of-residence", Person;
+    SpecialServiceRequest => "special-service-request", Person;
+    FrequentFlyerNumber => "frequent-flyer-number", Person;
+    TravelDetails => "travel-details", Person;
+    PaymentDetails => "payment-details", Person;
+    PlacePortOfOriginalEmbarkation => "place-port-of-original-embarkation", Person;
+    PlacePortOfClearance => "place-port-of-clearance", Person;
+    PlacePortOfOnwardForeignDestination => "place-port-of-onward-foreign-destination", Person;
+    PassengerNameRecordLocatorNumber => "passenger-name-record-locator-number", Person;
+    MobileApplicationId => "mobile-application-id", PayloadDelivery;
+    ChromeExtensionId => "chrome-extension-id", PayloadDelivery;
+    Cortex => "cortex", ExternalAnalysis;
+    Boolean => "boolean", OtherCategory;
+    Anonymised => "anonymised", OtherCategory;
+}
+
+attribute_categories! {
+    InternalReference => "Internal reference", [
+        "text", "link", "comment", "other", "hex", "anonymised", "git-commit-id",
+    ];
+    TargetingData => "Targeting data", [
+        "target-user", "target-email", "target-machine", "target-org", "target-location",
+        "target-external", "comment", "anonymised",
+    ];
+    AntivirusDetection => "Antivirus detection", [
+        "link", "comment", "text", "hex", "attachment", "other", "anonymised",
+    ];
+    PayloadDelivery => "Payload delivery", [
+        "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "sha512/224", "sha512/256",
+        "sha3-224", "sha3-256", "sha3-384", "sha3-512", "ssdeep", "imphash", "telfhash", "impfuzzy",
+        "authentihash", "vhash", "pehash", "tlsh", "cdhash", "filename", "filename|md5",
+        "filename|sha1", "filename|sha224", "filename|sha256", "filename|sha384", "filename|sha512",
+        "filename|sha512/224", "filename|sha512/256", "filename|sha3-224", "filename|sha3-256",
+        "filename|sha3-384", "filename|sha3-512", "filename|authentihash", "filename|vhash",
+        "filename|ssdeep", "filename|imphash", "filename|impfuzzy", "filename|pehash",
+        "filename|tlsh", "mac-address", "mac-eui-64", "ip-src", "ip-dst", "ip-dst|port",
+        "ip-src|port", "hostname", "domain", "email", "email-src", "email-dst", "email-subject",
+        "email-attachment", "email-body", "url", "user-agent", "AS", "pattern-in-file",
+        "pattern-in-traffic", "filename-pattern", "stix2-pattern", "yara", "sigma", "mime-type",
+        "attachment", "malware-sample", "link", "malware-type", "comment", "text", "hex",
+        "vulnerability", "cpe", "weakness", "x509-fingerprint-sha1", "x509-fingerprint-md5",
+        "x509-fingerprint-sha256", "ja3-fingerprint-md5", "jarm-fingerprint", "hassh-md5",
+        "hasshserver-md5", "other", "hostname|port", "email-dst-display-name",
+        "email-src-display-name", "email-header", "email-reply-to", "email-x-mailer",
+        "email-mime-boundary", "email-thread-index", "email-message-id", "mobile-application-id",
+        "chrome-extension-id", "whois-registrant-email", "anonymised",
+    ];
+    ArtifactsDropped => "Artifacts dropped", [
+        "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "sha512/224", "sha512/256",
+        "sha3-224", "sha3-256", "sha3-384", "sha3-512", "ssdeep", "imphash", "telfhash", "impfuzzy",
+        "authentihash", "vhash", "cdhash", "filename", "filename|md5", "filename|sha1",
+        "filename|sha224", "filename|sha256", "filename|sha384", "filename|sha512",
+        "filename|sha512/224", "filename|sha512/256", "filename|sha3-224", "filename|sha3-256",
+        "filename|sha3-384", "filename|sha3-512", "filename|authentihash", "filename|vhash",
+        "filename|ssdeep", "filename|imphash", "filename|impfuzzy", "filename|pehash",
+        "filename|tlsh", "regkey", "regkey|value", "pattern-in-file", "pattern-in-memory",
+        "filename-pattern", "pdb", "stix2-pattern", "yara", "sigma", "attachment", "malware-sample",
+        "named pipe", "mutex", "process-state", "windows-scheduled-task", "windows-service-name",
+        "windows-service-displayname", "comment", "text", "hex", "x509-fingerprint-sha1",
+        "x509-fingerprint-md5", "x509-fingerprint-sha256", "other", "cookie", "gene", "kusto-query",
+        "mime-type", "anonymised", "pgp-public-key", "pgp-private-key",
+    ];
+    PayloadInstallation => "Payload installation", [
+        "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "sha512/224", "sha512/256",
+        "sha3-224", "sha3-256", "sha3-384", "sha3-512", "ssdeep", "imphash", "telfhash", "impfuzzy",
+        "authentihash", "vhash", "pehash", "tlsh", "cdhash", "filename", "filename|md5",
+        "filename|sha1", "filename|sha224", "filename|sha256", "filename|sha384", "filename|sha512",
+        "filename|sha512/224", "filename|sha512/256", "filename|sha3-224", "filename|sha3-256",
+        "filename|sha3-384", "filename|sha3-512", "filename|authentihash", "filename|vhash",
+        "filename|ssdeep", "filename|imphash", "filename|impfuzzy", "filename|pehash",
+        "filename|tlsh", "pattern-in-file", "pattern-in-traffic", "pattern-in-memory",
+        "filename-pattern", "stix2-pattern", "yara", "sigma", "vulnerability", "cpe", "weakness",
+        "attachment", "malware-sample", "malware-type", "comment", "text", "hex",
+        "x509-fingerprint-sha1", "x509-fingerprint-md5", "x509-fingerprint-sha256",
+        "mobile-application-id", "chrome-extension-id", "other", "mime-type", "anonymised",
+    ];
+    PersistenceMechanism => "Persistence mechanism", [
+        "filename", "regkey", "regkey|value", "comment", "text", "other", "hex", "anonymised",
+    ];
+    NetworkActivity => "Network activity", [
+        "ip-src", "ip-dst", "ip-dst|port", "ip-src|port", "port", "hostname", "domain", "domain|ip",
+        "mac-address", "mac-eui-64", "email", "email-dst", "email-src", "eppn", "url", "uri",
+        "user-agent", "http-method", "AS", "snort", "pattern-in-file", "filename-pattern",
+        "stix2-pattern", "pattern-in-traffic", "attachment", "comment", "text",
+        "x509-fingerprint-sha1", "x509-fingerprint-md5", "x509-fingerprint-sha256",
+        "ja3-fingerprint-md5", "jarm-fingerprint", "hassh-md5", "hasshserver-md5", "other", "hex",
+        "cookie", "hostname|port", "bro", "zeek", "anonymised", "community-id", "email-subject",
+        "favicon-mmh3", "dkim", "dkim-signature", "ssh-fingerprint",
+    ];
+    PayloadType => "Payload type", [
+        "comment", "text", "other", "anonymised",
+    ];
+    Attribution => "Attribution", [
+        "threat-actor", "campaign-name", "campaign-id", "whois-registrant-phone",
+        "whois-registrant-email", "whois-registrant-name", "whois-registrant-org",
+        "whois-registrar", "whois-creation-date", "comment", "text", "x509-fingerprint-sha1",
+        "x509-fingerprint-md5", "x509-fingerprint-sha256", "dns-soa-email", "other", "anonymised",
+        "email",
+    ];
+    ExternalAnalysis => "External analysis", [
+        "md5", "sha1", "sha256", "sha3-224", "sha3-256", "sha3-384", "sha3-512", "filename",
+        "filename|md5", "filename|sha1", "filename|sha256", "filename|sha3-224",
+        "filename|sha3-256", "filename|sha3-384", "filename|sha3-512", "ip-src", "ip-dst",
+        "ip-dst|port", "ip-src|port", "mac-address", "mac-eui-64", "hostname", "domain",
+        "domain|ip", "url", "user-agent", "regkey", "regkey|value", "AS", "snort", "bro", "zeek",
+        "pattern-in-file", "pattern-in-traffic", "pattern-in-memory", "filename-pattern",
+        "vulnerability", "cpe", "weakness", "attachment", "malware-sample", "link", "comment",
+        "text", "x509-fingerprint-sha1", "x509-fingerprint-md5", "x509-fingerprint-sha256",
+        "ja3-fingerprint-md5", "jarm-fingerprint", "hassh-md5", "hasshserver-md5",
+        "github-repository", "other", "cortex", "anonymised", "community-id",
+    ];
+    FinancialFraud => "Financial fraud", [
+        "btc", "dash", "xmr", "iban", "bic", "bank-account-nr", "aba-rtn", "bin", "cc-number",
+        "prtn", "phone-number", "comment", "text", "other", "hex", "anonymised",
+    ];
+    SupportTool => "Support Tool", [
+        "link", "text", "attachment", "comment", "other", "hex", "anonymised",
+    ];
+    SocialNetwork => "Social network", [
+        "github-username", "github-repository", "github-organisation", "jabber-id", "twitter-id",
+        "email", "email-src", "email-dst", "eppn", "comment", "text", "other",
+        "whois-registrant-email", "anonymised", "pgp-public-key", "pgp-private-key",
+    ];
+    Person => "Person", [
+        "first-name", "middle-name", "last-name", "full-name", "date-of-birth", "place-of-birth",
+        "gender", "passport-number", "passport-country", "passport-expiration", "redress-number",
+        "nationality", "visa-number", "issue-date-of-the-visa", "primary-residence",
+        "country-of-residence", "special-service-request", "frequent-flyer-number",
+        "travel-details", "payment-details", "place-port-of-original-embarkation",
+        "place-port-of-clearance", "place-port-of-onward-foreign-destination",
+        "passenger-name-record-locator-number", "comment", "text", "other", "phone-number",
+        "identity-card-number", "anonymised", "email", "pgp-public-key", "pgp-private-key",
+    ];
+    OtherCategory => "Other", [
+        "comment", "text", "other", "size-in-bytes", "counter", "datetime", "cpe", "port", "float",
+        "hex", "phone-number", "boolean", "anonymised", "pgp-public-key", "pgp-private-key",
+    ];
+}
+
+/// The error returned when parsing a type name that is not known to this library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAttributeType(pub String);
+
+/// The error returned when parsing a category name that is not known to this library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAttributeCategory(pub String);
+
+impl AttributeType {
+    /// Returns true if the type is known to this library.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, AttributeType::Other(_))
+    }
+
+    /// Returns true for the types whose value consists of two parts separated by `|` (e.g.
+    /// `filename|sha256` or `ip-dst|port`).
+    pub fn is_composite(&self) -> bool {
+        self.is_known() && self.as_str().contains('|')
+    }
+
+    /// Returns the categories an attribute of this type can be put in.
+    pub fn categories(&self) -> Vec<AttributeCategory> {
+        AttributeCategory::ALL
+            .iter()
+            .filter(|category| category.allows(self))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns true if an attribute of this type can be put in the given category.
+    pub fn is_valid_category(&self, category: &AttributeCategory) -> bool {
+        category.allows(self)
+    }
+}
+
+impl AttributeCategory {
+    /// Returns true if the category is known to this library.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, AttributeCategory::Other(_))
+    }
+
+    /// Returns the types of the attributes that can be put in this category.
+    pub fn types(&self) -> Vec<AttributeType> {
+        self.type_names()
+            .iter()
+            .map(|name| AttributeType::from_name(name))
+            .collect()
+    }
+
+    /// Returns true if attributes of the given type can be put in this category.
+    pub fn allows(&self, kind: &AttributeType) -> bool {
+        kind.is_known() && self.type_names().contains(&kind.as_str())
+    }
+}
+
+impl AttributeType {
+    /// Creates an attribute type from a name sent by the server. Unknown names are kept as
+    /// `Other`, so that types added by newer MISP versions can still be read.
+    pub(crate) fn from_name(name: &str) -> AttributeType {
+        AttributeType::known(name).unwrap_or_else(|| AttributeType::Other(name.to_string()))
+    }
+}
+
+impl AttributeCategory {
+    /// Creates an attribute category from a name sent by the server. Unknown names are kept as
+    /// `Other`.
+    pub(crate) fn from_name(name: &str) -> AttributeCategory {
+        AttributeCategory::known(name).unwrap_or_else(|| AttributeCategory::Other(name.to_string()))
+    }
+}
+
+impl std::str::FromStr for AttributeType {
+    type Err = UnknownAttributeType;
+
+    /// Parses a type name. Unknown names are rejected, which catches typos before they reach
+    /// the server.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        AttributeType::known(name).ok_or_else(|| UnknownAttributeType(name.to_string()))
+    }
+}
+
+impl std::str::FromStr for AttributeCategory {
+    type Err = UnknownAttributeCategory;
+
+    /// Parses a category name. Unknown names are rejected.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        AttributeCategory::known(name).ok_or_else(|| UnknownAttributeCategory(name.to_string()))
+    }
+}
+
+impl PartialEq<str> for AttributeType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for AttributeType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<str> for AttributeCategory {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for AttributeCategory {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for AttributeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Display for UnknownAttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown attribute type '{}'", self.0)
+    }
+}
+
+impl Error for UnknownAttributeType {}
+
+impl fmt::Display for UnknownAttributeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown attribute category '{}'", self.0)
+    }
+}
+
+impl Error for UnknownAttributeCategory {}
+
+#[cfg(feature = "serde")]
+impl Serialize for AttributeType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AttributeType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|name| AttributeType::from_name(&name))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for AttributeCategory {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AttributeCategory {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|name| AttributeCategory::from_name(&name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute_type::{AttributeCategory, AttributeType, UnknownAttributeType};
+
+    #[test]
+    pub fn attribute_type_names() {
+        assert_eq!(
+            AttributeType::IpDstPort,
+            "ip-dst|port".parse::<AttributeType>().unwrap()
+        );
+        assert_eq!(
+            AttributeType::FilenameSha512_256,
+            "filename|sha512/256".parse::<AttributeType>().unwrap()
+        );
+        assert_eq!(
+            AttributeType::OtherType,
+            "other".parse::<AttributeType>().unwrap()
+        );
+        assert_eq!(
+            AttributeType::Other("ip-dts".into()),
+            AttributeType::from_name("ip-dts")
+        );
+        assert_eq!(
+            Err(UnknownAttributeType("ip-dts".into())),
+            "ip-dts".parse::<AttributeType>()
+        );
+        assert!(AttributeType::FilenameSha256.is_composite());
+        assert!(!AttributeType::Sha256.is_composite());
+        for kind in AttributeType::ALL {
+            assert_eq!(*kind, kind.as_str().parse::<AttributeType>().unwrap());
+        }
+        for category in AttributeCategory::ALL {
+            assert_eq!(
+                *category,
+                category.as_str().parse::<AttributeCategory>().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    pub fn attribute_type_categories() {
+        assert_eq!(
+            Some(AttributeCategory::NetworkActivity),
+            AttributeType::IpDst.default_category()
+        );
+        assert_eq!(None, AttributeType::from_name("ip-dts").default_category());
+        assert!(AttributeType::IpDst.is_valid_category(&AttributeCategory::PayloadDelivery));
+        assert!(!AttributeType::IpDst.is_valid_category(&AttributeCategory::Person));
+        assert!(AttributeCategory::FinancialFraud
+            .types()
+            .contains(&AttributeType::Btc));
+
+        // The default category of every type must be one of its valid categories
+        for kind in AttributeType::ALL {
+            let default = kind.default_category().unwrap();
+            assert!(kind.categories().contains(&default), "{}", kind);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn attribute_type_json() {
+        assert_eq!(
+            "\"filename|sha256\"",
+            serde_json::to_string(&AttributeType::FilenameSha256).unwrap()
+        );
+        assert_eq!(
+            AttributeCategory::OtherCategory,
+            serde_json::from_str::<AttributeCategory>("\"Other\"").unwrap()
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Typed attribute values, parsed according to the type of the attribute.
+use crate::attribute_type::AttributeType;
+use ipnet::IpNet;
+use std::error::Error;
+use std::fmt;
+use std::net::IpAddr;
+use url::Url;
+
+/// The value of an attribute, parsed according to its type.
+///
+/// Types that have no dedicated representation are returned as `Text`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    /// `ip-src` and `ip-dst` holding a single address.
+    IpAddress(IpAddr),
+    /// `ip-src` and `ip-dst` holding a network in CIDR notation (e.g. `198.51.100.0/24`).
+    IpNetwork(IpNet),
+    /// `ip-src|port` and `ip-dst|port`.
+    IpAddressPort(IpAddr, u16),
+    /// `domain` and `hostname`.
+    Domain(String),
+    /// `domain|ip`.
+    DomainIp(String, IpAddr),
+    /// `hostname|port`.
+    DomainPort(String, u16),
+    /// `url` and `link`.
+    Url(Url),
+    /// The email address types (`email`, `email-src`, `email-dst`, `target-email`, ...).
+    Email(String),
+    /// The hash and fingerprint types (`md5`, `sha256`, `ssdeep`, `x509-fingerprint-sha1`, ...).
+    Hash(Hash),
+    /// `filename|md5`, `filename|sha256`, ... split on the last `|`.
+    FilenameHash(String, Hash),
+    /// `port`.
+    Port(u16),
+    /// `AS`. Both `AS64496` and `64496` are accepted.
+    AsNumber(u32),
+    /// The other composite types (e.g. `regkey|value`), split on the first `|`.
+    Composite(String, String),
+    Text(String),
+}
+
+/// The algorithm of a hash or fingerprint attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha512_224,
+    Sha512_256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Imphash,
+    Authentihash,
+    Pehash,
+    Cdhash,
+    Telfhash,
+    Ssdeep,
+    Impfuzzy,
+    Tlsh,
+    Vhash,
+}
+
+/// A hash value together with its algorithm.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hash {
+    algorithm: HashAlgorithm,
+    value: String,
+}
+
+/// The reasons why an attribute value could not be parsed according to its type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValueError {
+    InvalidIpAddress(String),
+    InvalidPort(String),
+    InvalidAsNumber(String),
+    /// The hash has the wrong length or contains characters that are not hexadecimal.
+    InvalidHash {
+        algorithm: HashAlgorithm,
+        value: String,
+    },
+    InvalidDomain(String),
+    InvalidUrl {
+        value: String,
+        error: url::ParseError,
+    },
+    InvalidEmail(String),
+    /// The value of a composite type (e.g. `filename|md5`) does not contain a `|`.
+    MissingSeparator(String),
+}
+
+impl HashAlgorithm {
+    /// Returns the hash algorithm of a hash type, or of the hash part of a `filename|<hash>`
+    /// type.
+    pub fn for_type(kind: &AttributeType) -> Option<HashAlgorithm> {
+        use AttributeType::*;
+        let algorithm = match kind {
+            Md5 | FilenameMd5 | X509FingerprintMd5 | Ja3FingerprintMd5 | HasshMd5
+            | HasshserverMd5 => HashAlgorithm::Md5,
+            Sha1 | FilenameSha1 | X509FingerprintSha1 => HashAlgorithm::Sha1,
+            Sha224 | FilenameSha224 => HashAlgorithm::Sha224,
+            Sha256 | FilenameSha256 | X509FingerprintSha256 => HashAlgorithm::Sha256,
+            Sha384 | FilenameSha384 => HashAlgorithm::Sha384,
+            Sha512 | FilenameSha512 => HashAlgorithm::Sha512,
+            Sha512_224 | FilenameSha512_224 => HashAlgorithm::Sha512_224,
+            Sha512_256 | FilenameSha512_256 => HashAlgorithm::Sha512_256,
+            Sha3_224 | FilenameSha3_224 => HashAlgorithm::Sha3_224,
+            Sha3_256 | FilenameSha3_256 => HashAlgorithm::Sha3_256,
+            Sha3_384 | FilenameSha3_384 => HashAlgorithm::Sha3_384,
+            Sha3_512 | FilenameSha3_512 => HashAlgorithm::Sha3_512,
+            Imphash | FilenameImphash => HashAlgorithm::Imphash,
+            Authentihash | FilenameAuthentihash => HashAlgorithm::Authentihash,
+            Pehash | FilenamePehash => HashAlgorithm::Pehash,
+            Cdhash => HashAlgorithm::Cdhash,
+            Telfhash => HashAlgorithm::Telfhash,
+            Ssdeep | FilenameSsdeep => HashAlgorithm::Ssdeep,
+            Impfuzzy | FilenameImpfuzzy => HashAlgorithm::Impfuzzy,
+            Tlsh | FilenameTlsh => HashAlgorithm::Tlsh,
+            Vhash | FilenameVhash => HashAlgorithm::Vhash,
+            _ => return None,
+        };
+        Some(algorithm)
+    }
+
+    /// The length in hexadecimal characters of the hashes with a fixed length. Fuzzy hashes
+    /// (ssdeep, tlsh, ...) have no fixed length.
+    pub fn hex_length(&self) -> Option<usize> {
+        use HashAlgorithm::*;
+        match self {
+            Md5 | Imphash => Some(32),
+            Sha1 | Pehash | Cdhash => Some(40),
+            Sha224 | Sha512_224 | Sha3_224 => Some(56),
+            Sha256 | Sha512_256 | Sha3_256 | Authentihash => Some(64),
+            Sha384 | Sha3_384 => Some(96),
+            Sha512 | Sha3_512 => Some(128),
+            Telfhash | Ssdeep | Impfuzzy | Tlsh | Vhash => None,
+        }
+    }
+}
+
+impl Hash {
+    /// Checks the value of a hash. Hashes with a fixed length must have the right amount of
+    /// hexadecimal characters. The case of the value is kept.
+    pub fn new(
+        algorithm: HashAlgorithm,
+        value: impl Into<String>,
+    ) -> Result<Self, AttributeValueError> {
+        let value = value.into();
+        let valid = match algorithm.hex_length() {
+            Some(length) => value.len() == length && value.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !value.is_empty() && !value.chars().any(char::is_whitespace),
+        };
+        if valid {
+            Ok(Self { algorithm, value })
+        } else {
+            Err(AttributeValueError::InvalidHash { algorithm, value })
+        }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Splits a composite value on its first `|`.
+fn split_composite(value: &str) -> Result<(&str, &str), AttributeValueError> {
+    match value.find('|') {
+        Some(i) => Ok((&value[..i], &value[i + 1..])),
+        None => Err(AttributeValueError::MissingSeparator(value.to_string())),
+    }
+}
+
+fn parse_ip(value: &str) -> Result<IpAddr, AttributeValueError> {
+    value
+        .parse()
+        .map_err(|_| AttributeValueError::InvalidIpAddress(value.to_string()))
+}
+
+fn parse_port(value: &str) -> Result<u16, AttributeValueError> {
+    value
+        .parse()
+        .map_err(|_| AttributeValueError::InvalidPort(value.to_string()))
+}
+
+/// Removes the optional `AS` prefix (in any case) of an AS number, e.g. `AS64496`.
+pub(crate) fn strip_as_prefix(value: &str) -> &str {
+    match (value.get(..2), value.get(2..)) {
+        (Some(prefix), Some(number)) if prefix.eq_ignore_ascii_case("as") && !number.is_empty() => {
+            number
+        }
+        _ => value,
+    }
+}
+
+fn parse_as_number(value: &str) -> Result<u32, AttributeValueError> {
+    strip_as_prefix(value)
+        .parse()
+        .map_err(|_| AttributeValueError::InvalidAsNumber(value.to_string()))
+}
+
+/// Checks that the value is a domain name with at least two labels. A single trailing dot (fully
+/// qualified name) is accepted.
+pub(crate) fn is_domain(value: &str) -> bool {
+    let domain = value.strip_suffix('.').unwrap_or(value);
+    let labels: Vec<&str> = domain.split('.').collect();
+    !domain.is_empty()
+        && domain.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.chars().count() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+fn parse_domain(value: &str) -> Result<String, AttributeValueError> {
+    if is_domain(value) {
+        Ok(value.to_string())
+    } else {
+        Err(AttributeValueError::InvalidDomain(value.to_string()))
+    }
+}
+
+/// Checks that the value looks like an email address: a non-empty local part and a domain.
+pub(crate) fn is_email(value: &str) -> bool {
+    match value.rfind('@') {
+        Some(i) => {
+            let (local, domain) = (&value[..i], &value[i + 1..]);
+            !local.is_empty() && !local.chars().any(char::is_whitespace) && is_domain(domain)
+        }
+        None => false,
+    }
+}
+
+fn parse_url(value: &str) -> Result<Url, AttributeValueError> {
+    // Many URLs are shared without their scheme (e.g. `evil.example/payload`)
+    match Url::parse(value) {
+        Err(url::ParseError::RelativeUrlWithoutBase) => Url::parse(&format!("http://{}", value)),
+        result => result,
+    }
+    .map_err(|error| AttributeValueError::InvalidUrl {
+        value: value.to_string(),
+        error,
+    })
+}
+
+impl AttributeValue {
+    /// Parses the value of an attribute according to its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use misp_types::attribute_type::AttributeType;
+    /// # use misp_types::attribute_value::{AttributeValue, HashAlgorithm};
+    /// let value = AttributeValue::parse(
+    ///     &AttributeType::FilenameMd5,
+    ///     "invoice.exe|b1946ac92492d2347c6235b4d2611184",
+    /// )
+    /// .unwrap();
+    /// match value {
+    ///     AttributeValue::FilenameHash(filename, hash) => {
+    ///         assert_eq!("invoice.exe", filename);
+    ///         assert_eq!(HashAlgorithm::Md5, hash.algorithm());
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn parse(kind: &AttributeType, value: &str) -> Result<AttributeValue, AttributeValueError> {
+        use AttributeType::*;
+        if let Some(algorithm) = HashAlgorithm::for_type(kind) {
+            return if kind.as_str().starts_with("filename|") {
+                // Hashes never contain a '|', filenames might
+                let (filename, hash) = match value.rfind('|') {
+                    Some(i) => (&value[..i], &value[i + 1..]),
+                    None => return Err(AttributeValueError::MissingSeparator(value.to_string())),
+                };
+                Ok(AttributeValue::FilenameHash(
+                    filename.to_string(),
+                    Hash::new(algorithm, hash)?,
+                ))
+            } else {
+                Ok(AttributeValue::Hash(Hash::new(algorithm, value)?))
+            };
+        }
+        let parsed = match kind {
+            IpSrc | IpDst => match value.find('/') {
+                Some(_) => AttributeValue::IpNetwork(
+                    value
+                        .parse()
+                        .map_err(|_| AttributeValueError::InvalidIpAddress(value.to_string()))?,
+                ),
+                None => AttributeValue::IpAddress(parse_ip(value)?),
+            },
+            IpSrcPort | IpDstPort => {
+                let (ip, port) = split_composite(value)?;
+                AttributeValue::IpAddressPort(parse_ip(ip)?, parse_port(port)?)
+            }
+            Domain | Hostname => AttributeValue::Domain(parse_domain(value)?),
+            DomainIp => {
+                let (domain, ip) = split_composite(value)?;
+                AttributeValue::DomainIp(parse_domain(domain)?, parse_ip(ip)?)
+            }
+            HostnamePort => {
+                let (hostname, port) = split_composite(value)?;
+                AttributeValue::DomainPort(parse_domain(hostname)?, parse_port(port)?)
+            }
+            Url | Link => AttributeValue::Url(parse_url(value)?),
+            Email | EmailSrc | EmailDst | EmailReplyTo | TargetEmail | WhoisRegistrantEmail
+            | DnsSoaEmail => {
+                if !is_email(value) {
+                    return Err(AttributeValueError::InvalidEmail(value.to_string()));
+                }
+                AttributeValue::Email(value.to_string())
+            }
+            Port => AttributeValue::Port(parse_port(value)?),
+            As => AttributeValue::AsNumber(parse_as_number(value)?),
+            kind if kind.is_composite() => {
+                let (first, second) = split_composite(value)?;
+                AttributeValue::Composite(first.to_string(), second.to_string())
+            }
+            _ => AttributeValue::Text(value.to_string()),
+        };
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for AttributeValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use AttributeValueError::*;
+        match self {
+            InvalidIpAddress(v) => write!(f, "'{}' is not a valid IP address or network", v),
+            InvalidPort(v) => write!(f, "'{}' is not a valid port", v),
+            InvalidAsNumber(v) => write!(f, "'{}' is not a valid AS number", v),
+            InvalidHash { algorithm, value } => {
+                write!(f, "'{}' is not a valid {:?} hash", value, algorithm)
+            }
+            InvalidDomain(v) => write!(f, "'{}' is not a valid domain name", v),
+            InvalidUrl { value, error } => write!(f, "'{}' is not a valid URL: {}", value, error),
+            InvalidEmail(v) => write!(f, "'{}' is not a valid email address", v),
+            MissingSeparator(v) => write!(f, "'{}' is missing the '|' separator", v),
+        }
+    }
+}
+
+impl Error for AttributeValueError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute_type::AttributeType;
+    use crate::attribute_value::{AttributeValue, AttributeValueError, HashAlgorithm};
+
+    fn parse(kind: &str, value: &str) -> Result<AttributeValue, AttributeValueError> {
+        AttributeValue::parse(&kind.parse::<AttributeType>().unwrap(), value)
+    }
+
+    #[test]
+    pub fn parse_network_values() {
+        assert_eq!(
+            AttributeValue::IpAddress("2001:db8::1".parse().unwrap()),
+            parse("ip-dst", "2001:db8::1").unwrap()
+        );
+        assert_eq!(
+            AttributeValue::IpNetwork("198.51.100.0/24".parse().unwrap()),
+            parse("ip-src", "198.51.100.0/24").unwrap()
+        );
+        assert_eq!(
+            AttributeValue::IpAddressPort("198.51.100.7".parse().unwrap(), 443),
+            parse("ip-dst|port", "198.51.100.7|443").unwrap()
+        );
+        assert_eq!(
+            AttributeValue::DomainIp("evil.example".into(), "198.51.100.7".parse().unwrap()),
+            parse("domain|ip", "evil.example|198.51.100.7").unwrap()
+        );
+        assert_eq!(
+            AttributeValue::AsNumber(64496),
+            parse("AS", "AS64496").unwrap()
+        );
+        match parse("url", "evil.example/payload.exe").unwrap() {
+            AttributeValue::Url(url) => assert_eq!(Some("evil.example"), url.host_str()),
+            other => panic!("Unexpected value {:?}", other),
+        }
+        assert_eq!(
+            AttributeValue::Email("phish@evil.example".into()),
+            parse("email-src", "phish@evil.example").unwrap()
+        );
+    }
+
+    #[test]
+    pub fn parse_hashes() {
+        let sha256 = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        match parse("filename|sha256", &format!("a|b.exe|{}", sha256)).unwrap() {
+            AttributeValue::FilenameHash(filename, hash) => {
+                assert_eq!("a|b.exe", filename);
+                assert_eq!(HashAlgorithm::Sha256, hash.algorithm());
+                assert_eq!(sha256, hash.value());
+            }
+            other => panic!("Unexpected value {:?}", other),
+        }
+        match parse("ssdeep", "3:AXGBicFlgVNhBGcL6wCrFQEv:AXGHsNhxLsr2C").unwrap() {
+            AttributeValue::Hash(hash) => assert_eq!(HashAlgorithm::Ssdeep, hash.algorithm()),
+            other => panic!("Unexpected value {:?}", other),
+        }
+        match parse(
+            "x509-fingerprint-sha1",
+            "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709",
+        )
+        .unwrap()
+        {
+            AttributeValue::Hash(hash) => assert_eq!(HashAlgorithm::Sha1, hash.algorithm()),
+            other => panic!("Unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    pub fn reject_invalid_values() {
+        assert_eq!(
+            AttributeValueError::InvalidIpAddress("198.51.100.300".into()),
+            parse("ip-dst", "198.51.100.300").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::InvalidHash {
+                algorithm: HashAlgorithm::Md5,
+                value: "b1946ac92492d2347c6235b4d261118".into()
+            },
+            parse("md5", "b1946ac92492d2347c6235b4d261118").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::MissingSeparator("evil.example".into()),
+            parse("domain|ip", "evil.example").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::InvalidDomain("evil..example".into()),
+            parse("domain", "evil..example").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::InvalidPort("70000".into()),
+            parse("port", "70000").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::InvalidEmail("phish".into()),
+            parse("email-dst", "phish").unwrap_err()
+        );
+        assert_eq!(
+            AttributeValueError::InvalidAsNumber("€1".into()),
+            parse("AS", "€1").unwrap_err()
+        );
+    }
+}
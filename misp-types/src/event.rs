@@ -1,27 +1,30 @@
-use super::attribute::AttributeFull;
-use super::object::ObjectFull;
+use super::attribute::{AttributeFull, NewAttribute};
+use super::object::{NewObject, ObjectFull};
 use super::organization::{OrganizationIdentifier, OrganizationTemporary};
 
 use crate::analysis::Analysis;
 use crate::distribution::Distribution;
+use crate::galaxy::{Galaxy, GalaxyCluster};
+use crate::shadow_attribute::ShadowAttribute;
+use crate::tag::{find_tag, Tag};
 use crate::threat_level::ThreatLevel;
+use crate::validation::ValidationError;
 use chrono::{Date, DateTime, Utc};
 use std::fmt;
 use uuid::Uuid;
 
-#[cfg(feature = "serde")]
-use serde_json::Value;
-
 #[cfg(feature = "serde")]
 use super::serialization_helpers::{
-    date_to_mispdate, datetime_to_epoch, number_embedded_in_string,
+    date_to_mispdate, datetime_to_epoch, lenient_bool, number_embedded_in_string,
+    option_date_to_mispdate, tag_names,
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct EventIdentifier(pub u64);
 
+#[derive(Debug, Clone)]
 pub enum GenericEventIdentifier {
     Global(Uuid),
     Local(EventIdentifier),
@@ -70,15 +73,50 @@ pub struct EventFull {
     #[serde(rename = "Attribute")]
     attributes: Vec<AttributeFull>,
     #[serde(rename = "ShadowAttribute")]
-    shadow_attributes: Value,
+    #[serde(default)]
+    shadow_attributes: Vec<ShadowAttribute>,
     #[serde(rename = "RelatedEvent")]
-    related_events: Value,
+    #[serde(default)]
+    related_events: Vec<RelatedEvent>,
     #[serde(rename = "Galaxy")]
-    galaxies: Value,
+    #[serde(default)]
+    galaxies: Vec<Galaxy>,
     #[serde(rename = "Object")]
     objects: Vec<ObjectFull>,
     #[serde(rename = "Tag")]
-    tags: Value,
+    #[serde(default)]
+    tags: Vec<Tag>,
+}
+
+/// The summary of an event that correlates with another event, i.e. that shares at least one
+/// attribute value with it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RelatedEventSummary {
+    id: EventIdentifier,
+    uuid: Uuid,
+    info: String,
+    #[serde(with = "date_to_mispdate")]
+    date: Date<Utc>,
+    #[serde(with = "lenient_bool")]
+    published: bool,
+    analysis: Analysis,
+    threat_level_id: ThreatLevel,
+    distribution: Distribution,
+    #[serde(with = "datetime_to_epoch")]
+    timestamp: DateTime<Utc>,
+    org_id: OrganizationIdentifier,
+    orgc_id: OrganizationIdentifier,
+    #[serde(rename = "Org")]
+    org: OrganizationTemporary,
+    #[serde(rename = "Orgc")]
+    #[serde(default)]
+    orgc: Option<OrganizationTemporary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RelatedEvent {
+    #[serde(rename = "Event")]
+    event: RelatedEventSummary,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -87,6 +125,112 @@ pub struct EventFullEmbedded {
     pub event: EventFull,
 }
 
+/// An event that only exists locally and has not been sent to a MISP server yet.
+///
+/// Contrary to [`Event`](struct.Event.html), it does not contain any server-assigned fields such as
+/// the id, the owning organization or the attribute count. Optional fields that are not set are
+/// left out of the JSON, so that the server uses its defaults.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewEvent {
+    info: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "option_date_to_mispdate")]
+    date: Option<Date<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threat_level_id: Option<ThreatLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis: Option<Analysis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published: Option<bool>,
+    #[serde(rename = "Attribute")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<NewAttribute>,
+    #[serde(rename = "Object")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    objects: Vec<NewObject>,
+    #[serde(rename = "Tag")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(with = "tag_names")]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NewEventEmbedded {
+    #[serde(rename = "Event")]
+    pub event: NewEvent,
+}
+
+/// A change to the attributes or objects of an existing event.
+#[derive(Debug, Clone)]
+pub enum EntityChange<T> {
+    /// Adds a new entity. If the uuid of the entity already exists, the server updates it instead.
+    Add(T),
+    /// Removes the entity with the given uuid.
+    Remove(Uuid),
+}
+
+/// Partial changes to an existing event. Only the fields that were set are sent to the server,
+/// every other field is left untouched.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct EventUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "option_date_to_mispdate")]
+    date: Option<Date<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    threat_level_id: Option<ThreatLevel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis: Option<Analysis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disable_correlation: Option<bool>,
+    #[serde(rename = "Attribute")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<EntityChange<NewAttribute>>,
+    #[serde(rename = "Object")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    objects: Vec<EntityChange<NewObject>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct EventUpdateEmbedded {
+    #[serde(rename = "Event")]
+    pub event: EventUpdate,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for EntityChange<T> {
+    /// An added entity is serialized as is. A removed entity is serialized as
+    /// `{"uuid": "...", "deleted": true}`, which MISP interprets as a deletion.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Removal<'a> {
+            uuid: &'a Uuid,
+            deleted: bool,
+        }
+
+        match self {
+            EntityChange::Add(entity) => entity.serialize(serializer),
+            EntityChange::Remove(uuid) => Removal {
+                uuid,
+                deleted: true,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
 impl Serialize for EventIdentifier {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -231,6 +375,242 @@ impl Event {
     }
 }
 
+impl NewEvent {
+    /// Creates a new local event with the given info (the title of the event).
+    pub fn new(info: impl Into<String>) -> Self {
+        Self {
+            info: info.into(),
+            date: None,
+            uuid: None,
+            threat_level_id: None,
+            analysis: None,
+            distribution: None,
+            published: None,
+            attributes: Vec::new(),
+            objects: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn info(&self) -> &str {
+        &self.info
+    }
+
+    pub fn attributes(&self) -> &Vec<NewAttribute> {
+        &self.attributes
+    }
+
+    pub fn objects(&self) -> &Vec<NewObject> {
+        &self.objects
+    }
+
+    /// Sets the date of the event. If not set, the server uses the current date.
+    pub fn date(&mut self, date: Date<Utc>) -> &mut Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Uses a specific uuid instead of letting the server generate one.
+    pub fn uuid(&mut self, uuid: Uuid) -> &mut Self {
+        self.uuid = Some(uuid);
+        self
+    }
+
+    pub fn threat_level(&mut self, threat_level: ThreatLevel) -> &mut Self {
+        self.threat_level_id = Some(threat_level);
+        self
+    }
+
+    pub fn analysis(&mut self, analysis: Analysis) -> &mut Self {
+        self.analysis = Some(analysis);
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Publishes the event directly when it gets created.
+    pub fn published(&mut self, published: bool) -> &mut Self {
+        self.published = Some(published);
+        self
+    }
+
+    pub fn add_attribute(&mut self, attribute: NewAttribute) -> &mut Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    pub fn add_object(&mut self, object: NewObject) -> &mut Self {
+        self.objects.push(object);
+        self
+    }
+
+    /// Normalises the values of all the attributes of the event, including the attributes of its
+    /// objects. Stops at the first invalid value, so that the event can be fixed before the
+    /// server rejects it.
+    pub fn normalize(&mut self) -> Result<&mut Self, ValidationError> {
+        for attribute in &mut self.attributes {
+            attribute.normalize()?;
+        }
+        for object in &mut self.objects {
+            object.normalize()?;
+        }
+        Ok(self)
+    }
+
+    /// Attaches a tag to the event. The tag is referenced by its name.
+    pub fn add_tag(&mut self, name: impl Into<String>) -> &mut Self {
+        self.tags.push(name.into());
+        self
+    }
+}
+
+impl EventUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn info(&mut self, info: impl Into<String>) -> &mut Self {
+        self.info = Some(info.into());
+        self
+    }
+
+    pub fn date(&mut self, date: Date<Utc>) -> &mut Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub fn threat_level(&mut self, threat_level: ThreatLevel) -> &mut Self {
+        self.threat_level_id = Some(threat_level);
+        self
+    }
+
+    pub fn analysis(&mut self, analysis: Analysis) -> &mut Self {
+        self.analysis = Some(analysis);
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Locks the event, so that it is not modified by synchronisations anymore.
+    pub fn locked(&mut self, locked: bool) -> &mut Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    pub fn disable_correlation(&mut self, disable_correlation: bool) -> &mut Self {
+        self.disable_correlation = Some(disable_correlation);
+        self
+    }
+
+    pub fn add_attribute(&mut self, attribute: NewAttribute) -> &mut Self {
+        self.attributes.push(EntityChange::Add(attribute));
+        self
+    }
+
+    /// Removes the attribute with the given uuid from the event.
+    pub fn remove_attribute(&mut self, uuid: Uuid) -> &mut Self {
+        self.attributes.push(EntityChange::Remove(uuid));
+        self
+    }
+
+    pub fn add_object(&mut self, object: NewObject) -> &mut Self {
+        self.objects.push(EntityChange::Add(object));
+        self
+    }
+
+    /// Removes the object with the given uuid from the event.
+    pub fn remove_object(&mut self, uuid: Uuid) -> &mut Self {
+        self.objects.push(EntityChange::Remove(uuid));
+        self
+    }
+}
+
+impl RelatedEvent {
+    /// Returns the summary of the related event.
+    pub fn event(&self) -> &RelatedEventSummary {
+        &self.event
+    }
+
+    pub fn id(&self) -> EventIdentifier {
+        self.event.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.event.uuid
+    }
+
+    pub fn info(&self) -> &str {
+        &self.event.info
+    }
+
+    /// The organization that is currently handling the related event.
+    pub fn organization(&self) -> &OrganizationTemporary {
+        &self.event.org
+    }
+}
+
+impl RelatedEventSummary {
+    pub fn id(&self) -> EventIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn info(&self) -> &str {
+        &self.info
+    }
+
+    pub fn date(&self) -> &Date<Utc> {
+        &self.date
+    }
+
+    pub fn published(&self) -> bool {
+        self.published
+    }
+
+    pub fn analysis(&self) -> &Analysis {
+        &self.analysis
+    }
+
+    pub fn threat_level(&self) -> &ThreatLevel {
+        &self.threat_level_id
+    }
+
+    pub fn distribution(&self) -> &Distribution {
+        &self.distribution
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    // Returns the organization that is currently handling the event
+    pub fn organization_identifer(&self) -> OrganizationIdentifier {
+        self.org_id
+    }
+
+    // Returns the organization that initially created the event
+    pub fn organization_creator_identifier(&self) -> OrganizationIdentifier {
+        self.orgc_id
+    }
+
+    pub fn organization(&self) -> &OrganizationTemporary {
+        &self.org
+    }
+
+    pub fn organization_creator(&self) -> Option<&OrganizationTemporary> {
+        self.orgc.as_ref()
+    }
+}
+
 impl EventFull {
     pub fn attributes(&self) -> &Vec<AttributeFull> {
         &self.attributes
@@ -240,6 +620,75 @@ impl EventFull {
         &self.objects
     }
 
+    /// Returns the tags attached to the event itself. Tags of attributes are not included.
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    /// Returns true if a tag with the given name (e.g. `tlp:red`) is attached to the event.
+    pub fn has_tag(&self, name: impl AsRef<str>) -> bool {
+        self.tag(name).is_some()
+    }
+
+    /// Finds a tag attached to the event by its name.
+    pub fn tag(&self, name: impl AsRef<str>) -> Option<&Tag> {
+        find_tag(&self.tags, name.as_ref())
+    }
+
+    /// The events that share at least one attribute value with this event.
+    pub fn related_events(&self) -> &Vec<RelatedEvent> {
+        &self.related_events
+    }
+
+    /// The pending proposals for new attributes. Proposals to change existing attributes are
+    /// attached to the attributes themselves, see
+    /// [`AttributeFull::proposals`](../attribute/struct.AttributeFull.html#method.proposals).
+    pub fn proposals(&self) -> &Vec<ShadowAttribute> {
+        &self.shadow_attributes
+    }
+
+    pub fn galaxies(&self) -> &Vec<Galaxy> {
+        &self.galaxies
+    }
+
+    /// Returns the galaxy clusters attached to the event itself, across all galaxies.
+    pub fn galaxy_clusters(&self) -> impl Iterator<Item = &GalaxyCluster> {
+        self.galaxies.iter().flat_map(|g| g.clusters().iter())
+    }
+
+    /// Returns the MITRE ATT&CK attack patterns (techniques) attached to the event or to any of
+    /// its attributes, including the attributes of objects. Every technique is only returned once.
+    pub fn mitre_attack_techniques(&self) -> Vec<&GalaxyCluster> {
+        let mut techniques: Vec<&GalaxyCluster> = Vec::new();
+        let attribute_clusters = self
+            .attributes
+            .iter()
+            .chain(self.objects.iter().flat_map(|o| o.attributes().iter()))
+            .flat_map(|a| a.galaxy_clusters());
+        for cluster in self.galaxy_clusters().chain(attribute_clusters) {
+            if cluster.kind() == "mitre-attack-pattern"
+                && !techniques.iter().any(|t| t.uuid() == cluster.uuid())
+            {
+                techniques.push(cluster);
+            }
+        }
+        techniques
+    }
+
+    /// Finds an object of the event by its uuid.
+    pub fn object_by_uuid(&self, uuid: Uuid) -> Option<&ObjectFull> {
+        self.objects.iter().find(|o| o.uuid() == uuid)
+    }
+
+    /// Finds an attribute of the event by its uuid. Attributes that are part of an object are
+    /// included in the search.
+    pub fn attribute_by_uuid(&self, uuid: Uuid) -> Option<&AttributeFull> {
+        self.attributes
+            .iter()
+            .chain(self.objects.iter().flat_map(|o| o.attributes().iter()))
+            .find(|a| a.uuid() == uuid)
+    }
+
     //
     // The following functions are copied from Event. They just call the embedded event methods.
     // This is needed for easier access, so that you can use EventFull.date() instead of
@@ -322,3 +771,327 @@ impl EventFull {
         self.event.extends()
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::analysis::Analysis;
+    use crate::attribute::NewAttribute;
+    use crate::attribute_type::AttributeType;
+    use crate::event::{EventFull, EventUpdate, NewEvent};
+    use crate::object::NewObject;
+    use crate::threat_level::ThreatLevel;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    /// An event as returned by `events/view`, with a file object dropping another file object
+    /// and communicating with the event's ip-dst attribute.
+    pub(crate) const SAMPLE_EVENT: &str = r##"
+        {
+          "id": "7",
+          "org_id": "1",
+          "date": "2020-05-20",
+          "info": "Dropper campaign",
+          "uuid": "5ec4f1a0-0000-4000-8000-000000000007",
+          "published": false,
+          "analysis": "1",
+          "attribute_count": "4",
+          "orgc_id": "1",
+          "timestamp": "1590000000",
+          "distribution": "1",
+          "sharing_group_id": "0",
+          "proposal_email_lock": false,
+          "locked": false,
+          "threat_level_id": "2",
+          "publish_timestamp": "0",
+          "disable_correlation": false,
+          "extends_uuid": "",
+          "Org": {
+            "id": "1",
+            "name": "CIRCL",
+            "uuid": "55f6ea5e-2c60-40e5-964f-47a8950d210f"
+          },
+          "Orgc": {
+            "id": "1",
+            "name": "CIRCL",
+            "uuid": "55f6ea5e-2c60-40e5-964f-47a8950d210f"
+          },
+          "Attribute": [
+            {
+              "id": "11",
+              "event_id": "7",
+              "object_id": "0",
+              "object_relation": null,
+              "category": "Network activity",
+              "type": "ip-dst",
+              "value": "198.51.100.7",
+              "to_ids": true,
+              "uuid": "00000000-0000-4000-8000-000000000011",
+              "timestamp": "1590000000",
+              "distribution": "5",
+              "sharing_group_id": "0",
+              "comment": "",
+              "deleted": false,
+              "disable_correlation": false,
+              "first_seen": null,
+              "last_seen": null,
+              "Tag": [
+                {
+                  "id": "9",
+                  "name": "false-positive",
+                  "colour": "#ff0000",
+                  "exportable": true,
+                  "user_id": "0",
+                  "hide_tag": false,
+                  "numerical_value": null,
+                  "is_galaxy": false,
+                  "is_custom_galaxy": false,
+                  "local": 1
+                }
+              ]
+            }
+          ],
+          "ShadowAttribute": [],
+          "RelatedEvent": [
+            {
+              "Event": {
+                "id": "12",
+                "date": "2020-08-01",
+                "threat_level_id": "1",
+                "info": "Earlier phishing wave",
+                "published": true,
+                "uuid": "5f2c5d1e-0000-4000-8000-000000000012",
+                "analysis": "2",
+                "timestamp": "1596240000",
+                "distribution": "1",
+                "org_id": "1",
+                "orgc_id": "3",
+                "Org": {
+                  "id": "1",
+                  "name": "ORGNAME",
+                  "uuid": "5f2c5d1e-0000-4000-8000-000000000101"
+                },
+                "Orgc": {
+                  "id": "3",
+                  "name": "CIRCL",
+                  "uuid": "5f2c5d1e-0000-4000-8000-000000000103"
+                }
+              }
+            }
+          ],
+          "Galaxy": [],
+          "Object": [
+            {
+              "id": "21",
+              "name": "file",
+              "meta-category": "file",
+              "description": "File object",
+              "template_uuid": "688c46fb-5edb-40a3-8273-1af7923e2215",
+              "template_version": "22",
+              "event_id": "7",
+              "uuid": "00000000-0000-4000-8000-000000000021",
+              "timestamp": "1590000000",
+              "distribution": "5",
+              "sharing_group_id": "0",
+              "comment": "",
+              "deleted": false,
+              "first_seen": null,
+              "last_seen": null,
+              "ObjectReference": [
+                {
+                  "id": "31",
+                  "uuid": "00000000-0000-4000-8000-000000000031",
+                  "timestamp": "1590000000",
+                  "object_id": "21",
+                  "object_uuid": "00000000-0000-4000-8000-000000000021",
+                  "referenced_uuid": "00000000-0000-4000-8000-000000000022",
+                  "referenced_id": "22",
+                  "referenced_type": "1",
+                  "relationship_type": "drops",
+                  "comment": "",
+                  "deleted": false
+                },
+                {
+                  "id": "32",
+                  "uuid": "00000000-0000-4000-8000-000000000032",
+                  "timestamp": "1590000000",
+                  "object_id": "21",
+                  "referenced_uuid": "00000000-0000-4000-8000-000000000011",
+                  "referenced_id": "11",
+                  "referenced_type": "0",
+                  "relationship_type": "communicates-with",
+                  "comment": "",
+                  "deleted": false
+                }
+              ],
+              "Attribute": [
+                {
+                  "id": "12",
+                  "event_id": "7",
+                  "object_id": "21",
+                  "object_relation": "filename",
+                  "category": "Network activity",
+                  "type": "filename",
+                  "value": "dropper.exe",
+                  "to_ids": true,
+                  "uuid": "00000000-0000-4000-8000-000000000012",
+                  "timestamp": "1590000000",
+                  "distribution": "5",
+                  "sharing_group_id": "0",
+                  "comment": "",
+                  "deleted": false,
+                  "disable_correlation": false,
+                  "first_seen": null,
+                  "last_seen": null
+                }
+              ]
+            },
+            {
+              "id": "22",
+              "name": "file",
+              "meta-category": "file",
+              "description": "File object",
+              "template_uuid": "688c46fb-5edb-40a3-8273-1af7923e2215",
+              "template_version": "22",
+              "event_id": "7",
+              "uuid": "00000000-0000-4000-8000-000000000022",
+              "timestamp": "1590000000",
+              "distribution": "5",
+              "sharing_group_id": "0",
+              "comment": "",
+              "deleted": false,
+              "first_seen": null,
+              "last_seen": null,
+              "ObjectReference": [],
+              "Attribute": [
+                {
+                  "id": "13",
+                  "event_id": "7",
+                  "object_id": "22",
+                  "object_relation": "filename",
+                  "category": "Network activity",
+                  "type": "filename",
+                  "value": "payload.dll",
+                  "to_ids": true,
+                  "uuid": "00000000-0000-4000-8000-000000000013",
+                  "timestamp": "1590000000",
+                  "distribution": "5",
+                  "sharing_group_id": "0",
+                  "comment": "",
+                  "deleted": false,
+                  "disable_correlation": false,
+                  "first_seen": null,
+                  "last_seen": null
+                }
+              ]
+            }
+          ],
+          "Tag": [
+            {
+              "id": "3",
+              "name": "tlp:amber",
+              "colour": "#FFC000",
+              "exportable": true,
+              "user_id": "0",
+              "hide_tag": false,
+              "numerical_value": null,
+              "is_galaxy": false,
+              "is_custom_galaxy": false,
+              "local": 0
+            }
+          ]
+        }
+    "##;
+
+    pub(crate) fn sample_event() -> EventFull {
+        serde_json::from_str(SAMPLE_EVENT).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn new_event_to_json() {
+        let mut event = NewEvent::new("Phishing campaign");
+        event.threat_level(ThreatLevel::High).add_tag("tlp:amber");
+
+        let mut attribute = NewAttribute::new(AttributeType::IpDst, "198.51.100.7");
+        attribute.to_ids(true);
+        event.add_attribute(attribute);
+
+        let mut object = NewObject::new("domain-ip");
+        object.add_attribute(
+            "domain",
+            NewAttribute::new(AttributeType::Domain, "evil.example"),
+        );
+        event.add_object(object);
+
+        assert_eq!(
+            json!({
+                "info": "Phishing campaign",
+                "threat_level_id": "1",
+                "Attribute": [{"type": "ip-dst", "value": "198.51.100.7", "to_ids": true}],
+                "Object": [{
+                    "name": "domain-ip",
+                    "Attribute": [{
+                        "type": "domain",
+                        "value": "evil.example",
+                        "object_relation": "domain"
+                    }]
+                }],
+                "Tag": [{"name": "tlp:amber"}]
+            }),
+            serde_json::to_value(&event).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn event_update_to_json() {
+        let removed = Uuid::parse_str("5e6ba4f2-52f0-4a6c-8e7d-3f1c0a9b2d11").unwrap();
+        let mut update = EventUpdate::new();
+        update
+            .analysis(Analysis::Complete)
+            .add_attribute(NewAttribute::new(
+                AttributeType::Md5,
+                "d41d8cd98f00b204e9800998ecf8427e",
+            ))
+            .remove_attribute(removed);
+
+        assert_eq!(
+            json!({
+                "analysis": "2",
+                "Attribute": [
+                    {"type": "md5", "value": "d41d8cd98f00b204e9800998ecf8427e"},
+                    {"uuid": "5e6ba4f2-52f0-4a6c-8e7d-3f1c0a9b2d11", "deleted": true}
+                ]
+            }),
+            serde_json::to_value(&update).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn related_events() {
+        let event = sample_event();
+        let related = &event.related_events()[0];
+        assert_eq!(12, related.id().0);
+        assert_eq!("Earlier phishing wave", related.info());
+        assert_eq!("ORGNAME", related.organization().name());
+        assert_eq!(
+            "CIRCL",
+            related.event().organization_creator().unwrap().name()
+        );
+        assert!(related.event().published());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn event_tags() {
+        let event = sample_event();
+        assert!(event.has_tag("tlp:amber"));
+        assert!(!event.has_tag("tlp:red"));
+        assert!(!event.has_tag("false-positive"));
+
+        let attribute = &event.attributes()[0];
+        assert!(attribute.has_tag("false-positive"));
+        assert!(attribute.tag("false-positive").unwrap().local());
+    }
+}
@@ -0,0 +1,665 @@
+use crate::distribution::Distribution;
+use crate::tag::Tag;
+use std::collections::BTreeMap;
+use std::fmt;
+use uuid::Uuid;
+
+#[cfg(feature = "serde")]
+use super::serialization_helpers::{
+    galaxy_meta, lenient_bool, null_as_default, number_embedded_in_string,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GalaxyIdentifier(pub u64);
+
+#[derive(Debug, Clone)]
+pub enum GenericGalaxyIdentifier {
+    Global(Uuid),
+    Local(GalaxyIdentifier),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GalaxyClusterIdentifier(pub u64);
+
+/// A galaxy is a collection of clusters of the same kind, e.g. all the threat actors or all the
+/// MITRE ATT&CK attack patterns.
+/// [Documentation](https://www.misp-project.org/galaxy.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Galaxy {
+    id: GalaxyIdentifier,
+    uuid: Uuid,
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    namespace: String,
+    #[serde(rename = "GalaxyCluster")]
+    #[serde(default)]
+    clusters: Vec<GalaxyCluster>,
+}
+
+/// The way the server returns a galaxy: the clusters are next to the galaxy instead of inside of it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GalaxyEmbedded {
+    #[serde(rename = "Galaxy")]
+    pub galaxy: Galaxy,
+    #[serde(rename = "GalaxyCluster")]
+    #[serde(default)]
+    pub clusters: Vec<GalaxyCluster>,
+}
+
+/// A cluster of a galaxy, such as a single threat actor or an attack pattern. Attaching a cluster
+/// to an event or an attribute is done through its tag (see `tag_name`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GalaxyCluster {
+    id: GalaxyClusterIdentifier,
+    uuid: Uuid,
+    #[serde(rename = "type")]
+    kind: String,
+    value: String,
+    #[serde(default)]
+    tag_name: String,
+    #[serde(default)]
+    description: String,
+    galaxy_id: GalaxyIdentifier,
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    #[serde(with = "null_as_default")]
+    authors: Vec<String>,
+    #[serde(default)]
+    #[serde(with = "galaxy_meta")]
+    meta: BTreeMap<String, Vec<String>>,
+    #[serde(rename = "GalaxyElement")]
+    #[serde(default)]
+    elements: Vec<GalaxyElement>,
+    #[serde(rename = "GalaxyClusterRelation")]
+    #[serde(default)]
+    relations: Vec<GalaxyClusterRelation>,
+    /// True for the clusters that ship with MISP, false for custom clusters.
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    default: bool,
+    /// Only set when the cluster is attached to an event or an attribute.
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    local: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GalaxyClusterEmbedded {
+    #[serde(rename = "GalaxyCluster")]
+    pub cluster: GalaxyCluster,
+}
+
+/// A custom galaxy cluster that does not exist on the server yet.
+///
+/// Optional fields that are not set are left out of the JSON, so that the server uses its
+/// defaults.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewGalaxyCluster {
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(rename = "GalaxyElement")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    elements: Vec<GalaxyElement>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NewGalaxyClusterEmbedded {
+    #[serde(rename = "GalaxyCluster")]
+    pub cluster: NewGalaxyCluster,
+}
+
+/// A single key/value of the meta information of a cluster, as returned when fetching a galaxy
+/// cluster directly.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GalaxyElement {
+    key: String,
+    value: String,
+}
+
+/// A typed relation from a cluster to another cluster, e.g. a threat actor `uses` a tool.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GalaxyClusterRelation {
+    #[serde(default)]
+    galaxy_cluster_uuid: Option<Uuid>,
+    referenced_galaxy_cluster_uuid: Uuid,
+    #[serde(rename = "referenced_galaxy_cluster_type")]
+    relationship_type: String,
+    #[serde(rename = "Tag")]
+    #[serde(default)]
+    tags: Vec<Tag>,
+}
+
+impl Serialize for GalaxyIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for GalaxyIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number_embedded_in_string::deserialize(deserializer).map(|v| GalaxyIdentifier(v))
+    }
+}
+
+impl fmt::Display for GalaxyIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for GalaxyClusterIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for GalaxyClusterIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number_embedded_in_string::deserialize(deserializer).map(|v| GalaxyClusterIdentifier(v))
+    }
+}
+
+impl fmt::Display for GalaxyClusterIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl GenericGalaxyIdentifier {
+    pub fn to_url_id(&self) -> String {
+        match self {
+            GenericGalaxyIdentifier::Global(uuid) => uuid
+                .to_hyphenated()
+                .encode_lower(&mut Uuid::encode_buffer())
+                .to_string(),
+            GenericGalaxyIdentifier::Local(v) => v.to_string(),
+        }
+    }
+}
+
+impl Into<GenericGalaxyIdentifier> for u64 {
+    fn into(self) -> GenericGalaxyIdentifier {
+        GenericGalaxyIdentifier::Local(GalaxyIdentifier(self))
+    }
+}
+
+impl Into<GenericGalaxyIdentifier> for Uuid {
+    fn into(self) -> GenericGalaxyIdentifier {
+        GenericGalaxyIdentifier::Global(self)
+    }
+}
+
+impl Into<GenericGalaxyIdentifier> for GalaxyIdentifier {
+    fn into(self) -> GenericGalaxyIdentifier {
+        GenericGalaxyIdentifier::Local(self)
+    }
+}
+
+impl Into<GenericGalaxyIdentifier> for &Galaxy {
+    fn into(self) -> GenericGalaxyIdentifier {
+        GenericGalaxyIdentifier::Global(self.uuid)
+    }
+}
+
+impl Into<GalaxyClusterIdentifier> for u64 {
+    fn into(self) -> GalaxyClusterIdentifier {
+        GalaxyClusterIdentifier(self)
+    }
+}
+
+impl Into<GalaxyClusterIdentifier> for &GalaxyCluster {
+    fn into(self) -> GalaxyClusterIdentifier {
+        self.id
+    }
+}
+
+impl GalaxyEmbedded {
+    /// Returns the galaxy with its clusters moved inside of it.
+    pub fn into_galaxy(self) -> Galaxy {
+        let mut galaxy = self.galaxy;
+        if galaxy.clusters.is_empty() {
+            galaxy.clusters = self.clusters;
+        }
+        galaxy
+    }
+}
+
+impl Galaxy {
+    pub fn id(&self) -> GalaxyIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The type of the galaxy (e.g. `threat-actor` or `mitre-attack-pattern`). It is also the
+    /// predicate of the tags of its clusters.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn icon(&self) -> &str {
+        &self.icon
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The clusters of the galaxy. When the galaxy is attached to an event or an attribute, only
+    /// the attached clusters are included.
+    pub fn clusters(&self) -> &Vec<GalaxyCluster> {
+        &self.clusters
+    }
+
+    /// Finds a cluster of the galaxy by its value. The value is compared case insensitively.
+    pub fn cluster(&self, value: impl AsRef<str>) -> Option<&GalaxyCluster> {
+        self.clusters
+            .iter()
+            .find(|c| c.value.eq_ignore_ascii_case(value.as_ref()))
+    }
+}
+
+/// Returns true if `id` looks like a MITRE ATT&CK id: a one or two letter prefix followed by four
+/// digits and an optional sub-technique (e.g. `T1566`, `T1566.001`, `TA0001` or `S0002`).
+fn is_mitre_attack_id(id: &str) -> bool {
+    let (id, sub_technique) = match id.find('.') {
+        Some(i) => (&id[..i], Some(&id[i + 1..])),
+        None => (id, None),
+    };
+    let digits = id.trim_start_matches(|c: char| c.is_ascii_uppercase());
+    let prefix_len = id.len() - digits.len();
+    (1..=2).contains(&prefix_len)
+        && digits.len() == 4
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && match sub_technique {
+            Some(s) => s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()),
+            None => true,
+        }
+}
+
+impl GalaxyCluster {
+    pub fn id(&self) -> GalaxyClusterIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    /// The type of the galaxy the cluster belongs to (e.g. `threat-actor`).
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The name of the tag used to attach the cluster to events and attributes (e.g.
+    /// `misp-galaxy:threat-actor="Sofacy"`).
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn galaxy_identifier(&self) -> GalaxyIdentifier {
+        self.galaxy_id
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn authors(&self) -> &Vec<String> {
+        &self.authors
+    }
+
+    pub fn relations(&self) -> &Vec<GalaxyClusterRelation> {
+        &self.relations
+    }
+
+    pub fn default(&self) -> bool {
+        self.default
+    }
+
+    pub fn local(&self) -> bool {
+        self.local
+    }
+
+    /// Returns all the meta key/values of the cluster. Clusters fetched directly from the galaxy
+    /// API send their meta information as elements instead, those are merged in.
+    pub fn meta(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut meta: BTreeMap<&str, Vec<&str>> = self
+            .meta
+            .iter()
+            .map(|(key, values)| (key.as_str(), values.iter().map(String::as_str).collect()))
+            .collect();
+        if self.meta.is_empty() {
+            for element in &self.elements {
+                meta.entry(&element.key).or_default().push(&element.value);
+            }
+        }
+        meta
+    }
+
+    /// Returns the values of a single meta key (e.g. `country` or `refs`).
+    pub fn meta_values(&self, key: impl AsRef<str>) -> Vec<&str> {
+        let key = key.as_ref();
+        match self.meta.get(key) {
+            Some(values) => values.iter().map(String::as_str).collect(),
+            None => self
+                .elements
+                .iter()
+                .filter(|e| e.key == key)
+                .map(|e| e.value.as_str())
+                .collect(),
+        }
+    }
+
+    /// Other names under which the cluster is known (e.g. `APT 28` and `Fancy Bear` for Sofacy).
+    pub fn synonyms(&self) -> Vec<&str> {
+        self.meta_values("synonyms")
+    }
+
+    /// The ids of the cluster in external knowledge bases (e.g. `T1566.001` for MITRE ATT&CK).
+    pub fn external_ids(&self) -> Vec<&str> {
+        self.meta_values("external_id")
+    }
+
+    /// Returns the MITRE ATT&CK id of the cluster (e.g. `T1566.001`), if there is one.
+    ///
+    /// The id is taken from the external ids. Older clusters only contain the id in their value
+    /// (`Spearphishing Attachment - T1193`), which is used as fallback.
+    pub fn mitre_attack_id(&self) -> Option<&str> {
+        self.external_ids()
+            .into_iter()
+            .find(|id| is_mitre_attack_id(id))
+            .or_else(|| {
+                self.value
+                    .rsplit(" - ")
+                    .next()
+                    .filter(|id| is_mitre_attack_id(id))
+            })
+    }
+
+    /// Returns true if the cluster is part of one of the MITRE ATT&CK galaxies.
+    pub fn is_mitre_attack(&self) -> bool {
+        self.kind.starts_with("mitre-")
+    }
+}
+
+impl NewGalaxyCluster {
+    /// Creates a new custom cluster with the given value (e.g. the name of a threat actor).
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            uuid: None,
+            description: None,
+            source: None,
+            authors: Vec::new(),
+            distribution: None,
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn elements(&self) -> &Vec<GalaxyElement> {
+        &self.elements
+    }
+
+    /// Uses a specific uuid instead of letting the server generate one. Keeping the same uuid
+    /// allows to update the cluster later on.
+    pub fn uuid(&mut self, uuid: Uuid) -> &mut Self {
+        self.uuid = Some(uuid);
+        self
+    }
+
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn add_author(&mut self, author: impl Into<String>) -> &mut Self {
+        self.authors.push(author.into());
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Adds a meta key/value to the cluster (e.g. `country` and `RU`). A key can be added
+    /// multiple times.
+    pub fn add_meta(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.elements.push(GalaxyElement::new(key, value));
+        self
+    }
+
+    pub fn add_synonym(&mut self, synonym: impl Into<String>) -> &mut Self {
+        self.add_meta("synonyms", synonym)
+    }
+}
+
+impl GalaxyElement {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl GalaxyClusterRelation {
+    /// The uuid of the cluster the relation starts from. Only set when the relation is not
+    /// embedded in its cluster.
+    pub fn cluster_uuid(&self) -> Option<Uuid> {
+        self.galaxy_cluster_uuid
+    }
+
+    /// The uuid of the cluster the relation points to.
+    pub fn referenced_cluster_uuid(&self) -> Uuid {
+        self.referenced_galaxy_cluster_uuid
+    }
+
+    /// The kind of the relation (e.g. `uses` or `similar`).
+    pub fn relationship_type(&self) -> &str {
+        &self.relationship_type
+    }
+
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distribution::Distribution;
+    use crate::galaxy::{
+        is_mitre_attack_id, Galaxy, GalaxyCluster, NewGalaxyCluster, NewGalaxyClusterEmbedded,
+    };
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn json_to_galaxy() {
+        let galaxy: Galaxy = serde_json::from_str(
+            r#"{
+              "id": "12", "uuid": "c4e851fa-775f-11e7-8163-b774922098cd",
+              "name": "Attack Pattern", "type": "mitre-attack-pattern",
+              "description": "ATT&CK Tactic", "version": "9", "icon": "map",
+              "namespace": "mitre-attack", "enabled": true, "local_only": false,
+              "GalaxyCluster": [{
+                "id": "4578", "uuid": "2e34237d-8574-43f6-aace-ae2915de8597",
+                "collection_uuid": "dcb864dc-775f-11e7-9fbb-1f41b4996683",
+                "type": "mitre-attack-pattern",
+                "value": "Spearphishing Attachment - T1566.001",
+                "tag_name": "misp-galaxy:mitre-attack-pattern=\"Spearphishing Attachment - T1566.001\"",
+                "description": "Adversaries may send spearphishing emails.",
+                "galaxy_id": "12", "source": "https://github.com/mitre/cti",
+                "authors": ["MITRE"], "version": "26", "default": true, "locked": false,
+                "meta": {
+                  "external_id": ["T1566.001"],
+                  "kill_chain": ["mitre-attack:initial-access"],
+                  "synonyms": "Malicious Attachment",
+                  "refs": ["https://attack.mitre.org/techniques/T1566/001"]
+                },
+                "tag_id": "75", "local": false, "relationship_type": false,
+                "GalaxyClusterRelation": [{
+                  "id": "3", "galaxy_cluster_id": "4578",
+                  "referenced_galaxy_cluster_id": "4533",
+                  "referenced_galaxy_cluster_uuid": "a62a8db3-f23a-4d8f-afd6-9dbc77e7813b",
+                  "referenced_galaxy_cluster_type": "subtechnique-of",
+                  "galaxy_cluster_uuid": "2e34237d-8574-43f6-aace-ae2915de8597",
+                  "distribution": "3", "default": true
+                }]
+              }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(12, galaxy.id().0);
+        assert_eq!("mitre-attack-pattern", galaxy.kind());
+
+        let cluster = galaxy
+            .cluster("spearphishing attachment - t1566.001")
+            .unwrap();
+        assert!(cluster.is_mitre_attack());
+        assert_eq!(Some("T1566.001"), cluster.mitre_attack_id());
+        assert_eq!(vec!["Malicious Attachment"], cluster.synonyms());
+        assert_eq!(
+            vec!["mitre-attack:initial-access"],
+            cluster.meta_values("kill_chain")
+        );
+        assert_eq!(
+            "subtechnique-of",
+            cluster.relations()[0].relationship_type()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn json_to_cluster_with_elements() {
+        let cluster: GalaxyCluster = serde_json::from_str(
+            r#"{
+              "id": "8", "uuid": "5b4ee3ea-eee3-4c8e-8323-85ae32658754",
+              "type": "threat-actor", "value": "Sofacy",
+              "tag_name": "misp-galaxy:threat-actor=\"Sofacy\"",
+              "galaxy_id": "3", "authors": null, "meta": [],
+              "GalaxyElement": [
+                {"id": "1", "galaxy_cluster_id": "8", "key": "synonyms", "value": "APT 28"},
+                {"id": "2", "galaxy_cluster_id": "8", "key": "synonyms", "value": "Fancy Bear"},
+                {"id": "3", "galaxy_cluster_id": "8", "key": "country", "value": "RU"}
+              ]
+            }"#,
+        )
+        .unwrap();
+        assert!(cluster.authors().is_empty());
+        assert_eq!(vec!["APT 28", "Fancy Bear"], cluster.synonyms());
+        assert_eq!(vec!["RU"], cluster.meta()["country"]);
+        assert_eq!(None, cluster.mitre_attack_id());
+    }
+
+    #[test]
+    pub fn mitre_attack_ids() {
+        for id in &["T1566", "T1566.001", "TA0001", "S0002", "G0007", "M1049"] {
+            assert!(is_mitre_attack_id(id), "{}", id);
+        }
+        for id in &[
+            "Sofacy",
+            "T156",
+            "T1566.1",
+            "CVE-2020-0601",
+            "t1566",
+            "ABC1234",
+        ] {
+            assert!(!is_mitre_attack_id(id), "{}", id);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn new_cluster_to_json() {
+        let mut cluster = NewGalaxyCluster::new("Wizard Spider");
+        cluster
+            .description("Financially motivated group")
+            .distribution(Distribution::ThisCommunityOnly)
+            .add_synonym("UNC1878")
+            .add_meta("country", "RU");
+        assert_eq!(
+            serde_json::json!({"GalaxyCluster": {
+                "value": "Wizard Spider",
+                "description": "Financially motivated group",
+                "distribution": "1",
+                "GalaxyElement": [
+                    {"key": "synonyms", "value": "UNC1878"},
+                    {"key": "country", "value": "RU"}
+                ]
+            }}),
+            serde_json::to_value(NewGalaxyClusterEmbedded { cluster }).unwrap()
+        );
+    }
+}
@@ -3,10 +3,17 @@
 
 pub mod analysis;
 pub mod attribute;
+pub mod attribute_type;
+pub mod attribute_value;
 pub mod distribution;
 pub mod event;
+pub mod galaxy;
+pub mod machine_tag;
 pub mod object;
 pub mod organization;
 pub mod serialization_helpers;
 pub mod server_info;
+pub mod shadow_attribute;
+pub mod tag;
 pub mod threat_level;
+pub mod validation;
@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A tag following the machine tag (triple tag) syntax used by MISP taxonomies and galaxies:
+/// `namespace:predicate="value"`, e.g. `misp-galaxy:threat-actor="APT 28"`.
+///
+/// The value is optional: `tlp:red` is a machine tag with namespace `tlp` and predicate `red`.
+///
+/// # Examples
+///
+/// ```
+/// # use misp_types::machine_tag::MachineTag;
+/// let tag: MachineTag = r#"admiralty-scale:source-reliability="b""#.parse().unwrap();
+/// assert_eq!(tag.namespace(), "admiralty-scale");
+/// assert_eq!(tag.predicate(), "source-reliability");
+/// assert_eq!(tag.value(), Some("b"));
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct MachineTag {
+    namespace: String,
+    predicate: String,
+    value: Option<String>,
+}
+
+/// The reasons why a tag name is not a valid machine tag.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum MachineTagError {
+    /// There is no `:` separating the namespace from the predicate.
+    MissingSeparator,
+    /// The namespace (before the `:`) is empty.
+    EmptyNamespace,
+    /// The predicate (between the `:` and the `=`) is empty.
+    EmptyPredicate,
+    /// The namespace or the predicate contains a character that is not allowed (whitespace or `"`).
+    InvalidCharacter(char),
+    /// There is a `=`, but no value after it.
+    MissingValue,
+    /// The opening quote of the value is never closed.
+    UnterminatedValue,
+    /// The value contains an unknown escape sequence. Only `\"` and `\\` are supported.
+    InvalidEscape(char),
+    /// There are characters after the closing quote of the value.
+    TrailingCharacters(String),
+}
+
+impl MachineTag {
+    pub fn new(
+        namespace: impl Into<String>,
+        predicate: impl Into<String>,
+        value: Option<String>,
+    ) -> Self {
+        Self {
+            namespace: namespace.into(),
+            predicate: predicate.into(),
+            value,
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn predicate(&self) -> &str {
+        &self.predicate
+    }
+
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns true if the tag has the given namespace and predicate. Both are compared case
+    /// insensitively.
+    pub fn is(&self, namespace: impl AsRef<str>, predicate: impl AsRef<str>) -> bool {
+        self.namespace.eq_ignore_ascii_case(namespace.as_ref())
+            && self.predicate.eq_ignore_ascii_case(predicate.as_ref())
+    }
+}
+
+fn check_characters(part: &str) -> Result<(), MachineTagError> {
+    match part.chars().find(|c| c.is_whitespace() || *c == '"') {
+        Some(c) => Err(MachineTagError::InvalidCharacter(c)),
+        None => Ok(()),
+    }
+}
+
+fn parse_quoted_value(quoted: &str) -> Result<String, MachineTagError> {
+    let mut value = String::with_capacity(quoted.len());
+    let mut chars = quoted.char_indices();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped @ '"')) | Some((_, escaped @ '\\')) => value.push(escaped),
+                Some((_, other)) => return Err(MachineTagError::InvalidEscape(other)),
+                None => return Err(MachineTagError::UnterminatedValue),
+            },
+            '"' => {
+                let rest = chars.as_str();
+                return if rest.is_empty() {
+                    Ok(value)
+                } else {
+                    Err(MachineTagError::TrailingCharacters(rest.to_string()))
+                };
+            }
+            _ => value.push(c),
+        }
+    }
+    Err(MachineTagError::UnterminatedValue)
+}
+
+impl FromStr for MachineTag {
+    type Err = MachineTagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, rest) = match s.find(':') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => return Err(MachineTagError::MissingSeparator),
+        };
+        // Only the first '=' separates the predicate from the value, which may contain any
+        // character (including ':' and '=').
+        let (predicate, value) = match rest.find('=') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+
+        if namespace.is_empty() {
+            return Err(MachineTagError::EmptyNamespace);
+        }
+        if predicate.is_empty() {
+            return Err(MachineTagError::EmptyPredicate);
+        }
+        check_characters(namespace)?;
+        check_characters(predicate)?;
+
+        let value = match value {
+            None => None,
+            Some("") => return Err(MachineTagError::MissingValue),
+            Some(v) if v.starts_with('"') => Some(parse_quoted_value(&v[1..])?),
+            Some(v) => match v.find('"') {
+                Some(_) => return Err(MachineTagError::InvalidCharacter('"')),
+                None => Some(v.to_string()),
+            },
+        };
+
+        Ok(MachineTag::new(namespace, predicate, value))
+    }
+}
+
+impl fmt::Display for MachineTag {
+    /// Formats the machine tag the way MISP names tags. The value is always quoted.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.predicate)?;
+        if let Some(value) = &self.value {
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+            write!(f, "=\"{}\"", escaped)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MachineTagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use MachineTagError::*;
+        match self {
+            MissingSeparator => write!(f, "Missing ':' between namespace and predicate"),
+            EmptyNamespace => write!(f, "The namespace is empty"),
+            EmptyPredicate => write!(f, "The predicate is empty"),
+            InvalidCharacter(c) => write!(f, "Invalid character {:?} in machine tag", c),
+            MissingValue => write!(f, "Missing value after '='"),
+            UnterminatedValue => write!(f, "The value is missing its closing quote"),
+            InvalidEscape(c) => write!(f, "Invalid escape sequence '\\{}' in value", c),
+            TrailingCharacters(s) => write!(f, "Unexpected characters after the value: {}", s),
+        }
+    }
+}
+
+impl Error for MachineTagError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::machine_tag::{MachineTag, MachineTagError};
+
+    #[test]
+    pub fn parse_machine_tags() {
+        assert_eq!(
+            MachineTag::new("tlp", "red", None),
+            "tlp:red".parse().unwrap()
+        );
+        assert_eq!(
+            MachineTag::new("misp-galaxy", "threat-actor", Some("APT 28".into())),
+            r#"misp-galaxy:threat-actor="APT 28""#.parse().unwrap()
+        );
+        assert_eq!(
+            MachineTag::new(
+                "osint",
+                "source-type",
+                Some("https://example.com/a=b".into())
+            ),
+            r#"osint:source-type="https://example.com/a=b""#.parse().unwrap()
+        );
+        assert_eq!(
+            MachineTag::new("ns", "pred", Some(r#"say "hi" \o/"#.into())),
+            r#"ns:pred="say \"hi\" \\o/""#.parse().unwrap()
+        );
+        assert_eq!(
+            MachineTag::new("ns", "pred", Some("unquoted".into())),
+            "ns:pred=unquoted".parse().unwrap()
+        );
+        assert_eq!(
+            MachineTag::new("ns", "pred", Some("".into())),
+            r#"ns:pred="""#.parse().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn reject_malformed_machine_tags() {
+        let err = |s: &str| s.parse::<MachineTag>().unwrap_err();
+        assert_eq!(MachineTagError::MissingSeparator, err("malware"));
+        assert_eq!(MachineTagError::EmptyNamespace, err(":red"));
+        assert_eq!(MachineTagError::EmptyPredicate, err("tlp:"));
+        assert_eq!(MachineTagError::EmptyPredicate, err(r#"tlp:="x""#));
+        assert_eq!(MachineTagError::InvalidCharacter(' '), err("my tag:red"));
+        assert_eq!(MachineTagError::MissingValue, err("ns:pred="));
+        assert_eq!(MachineTagError::UnterminatedValue, err(r#"ns:pred="open"#));
+        assert_eq!(
+            MachineTagError::InvalidEscape('n'),
+            err(r#"ns:pred="a\nb""#)
+        );
+        assert_eq!(
+            MachineTagError::TrailingCharacters("x".into()),
+            err(r#"ns:pred="a"x"#)
+        );
+    }
+
+    #[test]
+    pub fn format_machine_tags() {
+        for tag in &[
+            "tlp:amber",
+            r#"misp-galaxy:threat-actor="APT 28""#,
+            r#"ns:pred="say \"hi\" \\o/""#,
+        ] {
+            assert_eq!(*tag, tag.parse::<MachineTag>().unwrap().to_string());
+        }
+        assert_eq!(
+            r#"ns:pred="unquoted""#,
+            "ns:pred=unquoted"
+                .parse::<MachineTag>()
+                .unwrap()
+                .to_string()
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use crate::attribute::AttributeFull;
-use crate::event::EventIdentifier;
+use crate::attribute::{AttributeFull, NewAttribute};
+use crate::attribute_type::AttributeType;
+use crate::distribution::Distribution;
+use crate::event::{EntityChange, EventFull, EventIdentifier};
+use crate::tag::{find_tag, Tag};
+use crate::validation::ValidationError;
 use chrono::{DateTime, Utc};
 use core::fmt;
+use std::error::Error;
 use uuid::Uuid;
 
 #[cfg(feature = "serde")]
@@ -10,13 +15,12 @@ use super::serialization_helpers::{
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-#[cfg(feature = "serde")]
-use serde_json::Value;
 
 #[derive(Debug, Copy, Clone)]
 pub struct ObjectIdentifier(pub u64);
 
-pub enum GenericEventIdentifier {
+#[derive(Debug, Clone)]
+pub enum GenericObjectIdentifier {
     Global(Uuid),
     Local(ObjectIdentifier),
 }
@@ -48,14 +52,219 @@ pub struct ObjectFull {
     #[serde(flatten)]
     object: Object,
 
+    #[serde(default)]
     #[serde(with = "option_datetime_to_epoch")]
     first_seen: Option<DateTime<Utc>>,
+    #[serde(default)]
     #[serde(with = "option_datetime_to_epoch")]
     last_seen: Option<DateTime<Utc>>,
     #[serde(rename = "ObjectReference")]
-    object_reference: Value,
+    #[serde(default)]
+    references: Vec<ObjectReference>,
     #[serde(rename = "Attribute")]
     attributes: Vec<AttributeFull>,
+    #[serde(rename = "Tag")]
+    #[serde(default)]
+    tags: Vec<Tag>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObjectFullEmbedded {
+    #[serde(rename = "Object")]
+    pub object: ObjectFull,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ObjectReferenceIdentifier(pub u64);
+
+/// The kind of entity an object reference is pointing to.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum ReferencedType {
+    Attribute,
+    Object,
+    Unsupported(u16),
+}
+
+/// A relationship between an object and another object or attribute of the same event (e.g. a
+/// `file` object that `drops` another `file` object).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ObjectReference {
+    id: ObjectReferenceIdentifier,
+    uuid: Uuid,
+    #[serde(with = "datetime_to_epoch")]
+    timestamp: DateTime<Utc>,
+    object_id: ObjectIdentifier,
+    #[serde(default)]
+    object_uuid: Option<Uuid>,
+    referenced_uuid: Uuid,
+    referenced_type: ReferencedType,
+    relationship_type: String,
+    #[serde(default)]
+    comment: String,
+    #[serde(default)]
+    deleted: bool,
+}
+
+/// The entity an object reference points to, resolved inside of the event owning the object.
+#[derive(Debug, Clone, Copy)]
+pub enum ReferenceTarget<'a> {
+    Attribute(&'a AttributeFull),
+    Object(&'a ObjectFull),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObjectReferenceEmbedded {
+    #[serde(rename = "ObjectReference")]
+    pub reference: ObjectReference,
+}
+
+/// A reference from an existing object to another object or attribute that has not been sent to
+/// the MISP server yet.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewObjectReference {
+    object_uuid: Uuid,
+    referenced_uuid: Uuid,
+    relationship_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NewObjectReferenceEmbedded {
+    #[serde(rename = "ObjectReference")]
+    pub reference: NewObjectReference,
+}
+
+/// Partial changes to an existing object. Only the fields that were set are sent to the server,
+/// every other field is left untouched.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ObjectUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(rename = "Attribute")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<EntityChange<NewAttribute>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ObjectUpdateEmbedded {
+    #[serde(rename = "Object")]
+    pub object: ObjectUpdate,
+}
+
+/// Describes an object template of the [MISP object library](https://github.com/MISP/misp-objects):
+/// its name, its uuid and the MISP type of each of the object relations it defines.
+#[derive(Debug)]
+pub struct ObjectTemplate {
+    pub name: &'static str,
+    pub uuid: &'static str,
+    pub meta_category: &'static str,
+    /// Pairs of (object_relation, attribute type)
+    pub relations: &'static [(&'static str, &'static str)],
+}
+
+/// The `file` template, describing a file with its hashes.
+pub const FILE_TEMPLATE: ObjectTemplate = ObjectTemplate {
+    name: "file",
+    uuid: "688c46fb-5edb-40a3-8273-1af7923e2215",
+    meta_category: "file",
+    relations: &[
+        ("filename", "filename"),
+        ("md5", "md5"),
+        ("sha1", "sha1"),
+        ("sha224", "sha224"),
+        ("sha256", "sha256"),
+        ("sha384", "sha384"),
+        ("sha512", "sha512"),
+        ("ssdeep", "ssdeep"),
+        ("tlsh", "tlsh"),
+        ("authentihash", "authentihash"),
+        ("size-in-bytes", "size-in-bytes"),
+        ("entropy", "float"),
+        ("mimetype", "mime-type"),
+        ("path", "text"),
+        ("fullpath", "text"),
+        ("state", "text"),
+        ("malware-sample", "malware-sample"),
+        ("attachment", "attachment"),
+        ("text", "text"),
+    ],
+};
+
+/// The `domain-ip` template, describing a domain and the IPs it resolves to.
+pub const DOMAIN_IP_TEMPLATE: ObjectTemplate = ObjectTemplate {
+    name: "domain-ip",
+    uuid: "43b3b146-77eb-4931-b4cc-b66c60f28734",
+    meta_category: "network",
+    relations: &[
+        ("domain", "domain"),
+        ("hostname", "hostname"),
+        ("ip", "ip-dst"),
+        ("port", "port"),
+        ("registration-date", "datetime"),
+        ("first-seen", "datetime"),
+        ("last-seen", "datetime"),
+        ("text", "text"),
+    ],
+};
+
+/// The `url` template, describing an URL and its components.
+pub const URL_TEMPLATE: ObjectTemplate = ObjectTemplate {
+    name: "url",
+    uuid: "60efb77b-40b5-4c46-871b-ed1ed999fce5",
+    meta_category: "network",
+    relations: &[
+        ("url", "url"),
+        ("scheme", "text"),
+        ("credential", "text"),
+        ("subdomain", "text"),
+        ("domain", "domain"),
+        ("domain_without_tld", "text"),
+        ("tld", "text"),
+        ("host", "hostname"),
+        ("ip", "ip-dst"),
+        ("port", "port"),
+        ("resource_path", "text"),
+        ("query_string", "text"),
+        ("fragment", "text"),
+        ("first-seen", "datetime"),
+        ("last-seen", "datetime"),
+        ("text", "text"),
+    ],
+};
+
+/// Error returned when an object relation is not defined by the template of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownObjectRelation {
+    pub template: String,
+    pub relation: String,
+}
+
+/// An object that only exists locally and has not been sent to a MISP server yet.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewObject {
+    name: String,
+    #[serde(rename = "meta-category")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta_category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_uuid: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distribution: Option<Distribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    #[serde(rename = "Attribute")]
+    attributes: Vec<NewAttribute>,
+    #[serde(skip)]
+    template: Option<&'static ObjectTemplate>,
 }
 
 impl Serialize for ObjectIdentifier {
@@ -82,11 +291,166 @@ impl fmt::Display for ObjectIdentifier {
     }
 }
 
+impl Serialize for ObjectReferenceIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectReferenceIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number_embedded_in_string::deserialize(deserializer).map(|v| ObjectReferenceIdentifier(v))
+    }
+}
+
+impl fmt::Display for ObjectReferenceIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u16> for ReferencedType {
+    /// Creates a ReferencedType from the number used by MISP
+    fn from(referenced_type: u16) -> ReferencedType {
+        match referenced_type {
+            0 => ReferencedType::Attribute,
+            1 => ReferencedType::Object,
+            _ => ReferencedType::Unsupported(referenced_type),
+        }
+    }
+}
+
+impl From<&ReferencedType> for u16 {
+    /// Converts a ReferencedType to the number used by MISP.
+    fn from(referenced_type: &ReferencedType) -> u16 {
+        match referenced_type {
+            ReferencedType::Attribute => 0,
+            ReferencedType::Object => 1,
+            ReferencedType::Unsupported(v) => *v,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ReferencedType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        number_embedded_in_string::serialize(u16::from(self), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ReferencedType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(number_embedded_in_string::deserialize::<u16, D>(deserializer)?.into())
+    }
+}
+
+impl fmt::Display for UnknownObjectRelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The object template '{}' does not define the relation '{}'",
+            self.template, self.relation
+        )
+    }
+}
+
+impl Error for UnknownObjectRelation {}
+
+impl GenericObjectIdentifier {
+    pub fn to_url_id(&self) -> String {
+        match self {
+            GenericObjectIdentifier::Global(uuid) => uuid
+                .to_hyphenated()
+                .encode_lower(&mut Uuid::encode_buffer())
+                .to_string(),
+            GenericObjectIdentifier::Local(v) => v.to_string(),
+        }
+    }
+}
+
+impl Into<GenericObjectIdentifier> for u64 {
+    fn into(self) -> GenericObjectIdentifier {
+        GenericObjectIdentifier::Local(ObjectIdentifier(self))
+    }
+}
+
+impl Into<GenericObjectIdentifier> for Uuid {
+    fn into(self) -> GenericObjectIdentifier {
+        GenericObjectIdentifier::Global(self)
+    }
+}
+
+impl Into<GenericObjectIdentifier> for Object {
+    fn into(self) -> GenericObjectIdentifier {
+        GenericObjectIdentifier::Global(self.uuid)
+    }
+}
+
+impl Into<GenericObjectIdentifier> for ObjectFull {
+    fn into(self) -> GenericObjectIdentifier {
+        GenericObjectIdentifier::Global(self.object.uuid)
+    }
+}
+
+impl Into<GenericObjectIdentifier> for ObjectFullEmbedded {
+    fn into(self) -> GenericObjectIdentifier {
+        GenericObjectIdentifier::Global(self.object.object.uuid)
+    }
+}
+
+impl ObjectTemplate {
+    /// Returns the MISP attribute type of an object relation defined by this template.
+    pub fn attribute_type(&self, relation: &str) -> Option<&'static str> {
+        self.relations
+            .iter()
+            .find(|(r, _)| *r == relation)
+            .map(|(_, kind)| *kind)
+    }
+}
+
 impl Object {
+    pub fn id(&self) -> ObjectIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn event_identifier(&self) -> EventIdentifier {
+        self.event_id
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn meta_category(&self) -> &str {
+        &self.meta_category
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    /// Returns true if the object was soft deleted.
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
     pub fn description(&self) -> &str {
         &self.description
     }
@@ -96,11 +460,322 @@ impl Object {
     }
 }
 
+impl NewObject {
+    /// Creates a new local object given the name of its template (e.g. `file`).
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            meta_category: None,
+            description: None,
+            template_uuid: None,
+            template_version: None,
+            uuid: None,
+            distribution: None,
+            comment: None,
+            attributes: Vec::new(),
+            template: None,
+        }
+    }
+
+    /// Creates a new local object based on a template of the MISP object library. The attribute
+    /// types of the object relations are then known, so that values can be added with
+    /// [`add_value`](#method.add_value).
+    pub fn from_template(template: &'static ObjectTemplate) -> Self {
+        let mut object = Self::new(template.name);
+        object.meta_category = Some(template.meta_category.to_string());
+        object.template_uuid = Uuid::parse_str(template.uuid).ok();
+        object.template = Some(template);
+        object
+    }
+
+    /// Creates a `file` object with the given filename.
+    pub fn file(filename: impl Into<String>) -> Self {
+        let mut object = Self::from_template(&FILE_TEMPLATE);
+        object.add_attribute(
+            "filename",
+            NewAttribute::new(AttributeType::Filename, filename),
+        );
+        object
+    }
+
+    /// Creates a `domain-ip` object with the given domain.
+    pub fn domain_ip(domain: impl Into<String>) -> Self {
+        let mut object = Self::from_template(&DOMAIN_IP_TEMPLATE);
+        object.add_attribute("domain", NewAttribute::new(AttributeType::Domain, domain));
+        object
+    }
+
+    /// Creates an `url` object with the given url.
+    pub fn url(url: impl Into<String>) -> Self {
+        let mut object = Self::from_template(&URL_TEMPLATE);
+        object.add_attribute("url", NewAttribute::new(AttributeType::Url, url));
+        object
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn attributes(&self) -> &Vec<NewAttribute> {
+        &self.attributes
+    }
+
+    pub fn meta_category(&mut self, meta_category: impl Into<String>) -> &mut Self {
+        self.meta_category = Some(meta_category.into());
+        self
+    }
+
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Links the object to a specific version of an object template.
+    pub fn template(&mut self, template_uuid: Uuid, template_version: u64) -> &mut Self {
+        self.template_uuid = Some(template_uuid);
+        self.template_version = Some(template_version);
+        self
+    }
+
+    /// Uses a specific uuid instead of letting the server generate one.
+    pub fn uuid(&mut self, uuid: Uuid) -> &mut Self {
+        self.uuid = Some(uuid);
+        self
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Adds an attribute to the object. The attribute's object_relation is set to `relation`.
+    pub fn add_attribute(
+        &mut self,
+        relation: impl Into<String>,
+        mut attribute: NewAttribute,
+    ) -> &mut Self {
+        attribute.relation(relation);
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Normalises the values of all the attributes of the object. Stops at the first invalid
+    /// value.
+    pub fn normalize(&mut self) -> Result<&mut Self, ValidationError> {
+        for attribute in &mut self.attributes {
+            attribute.normalize()?;
+        }
+        Ok(self)
+    }
+
+    /// Adds a value for an object relation of the object's template. The attribute type is
+    /// looked up in the template.
+    ///
+    /// Fails if the object was not created from a template or if the template does not define
+    /// the relation.
+    pub fn add_value(
+        &mut self,
+        relation: impl AsRef<str>,
+        value: impl Into<String>,
+    ) -> Result<&mut Self, UnknownObjectRelation> {
+        let relation = relation.as_ref();
+        let kind = self
+            .template
+            .and_then(|t| t.attribute_type(relation))
+            .ok_or_else(|| UnknownObjectRelation {
+                template: self.name.clone(),
+                relation: relation.to_string(),
+            })?;
+        Ok(self.add_attribute(
+            relation,
+            NewAttribute::new(AttributeType::from_name(kind), value),
+        ))
+    }
+}
+
+impl NewObjectReference {
+    /// Creates a new reference from the object `object_uuid` to the object or attribute
+    /// `referenced_uuid` (e.g. `drops`, `communicates-with`, `related-to`).
+    pub fn new(
+        object_uuid: Uuid,
+        referenced_uuid: Uuid,
+        relationship_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            object_uuid,
+            referenced_uuid,
+            relationship_type: relationship_type.into(),
+            comment: None,
+        }
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+impl ObjectReference {
+    pub fn id(&self) -> ObjectReferenceIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    /// Returns the identifier of the object the reference originates from.
+    pub fn object_identifier(&self) -> ObjectIdentifier {
+        self.object_id
+    }
+
+    /// Returns the uuid of the object the reference originates from. Not every MISP version
+    /// sends it.
+    pub fn object_uuid(&self) -> Option<Uuid> {
+        self.object_uuid
+    }
+
+    /// Returns the uuid of the object or attribute the reference points to.
+    pub fn referenced_uuid(&self) -> Uuid {
+        self.referenced_uuid
+    }
+
+    pub fn referenced_type(&self) -> &ReferencedType {
+        &self.referenced_type
+    }
+
+    pub fn relationship_type(&self) -> &str {
+        &self.relationship_type
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Follows the reference to the object or attribute it points to. `event` has to be the
+    /// event owning the referencing object.
+    ///
+    /// Returns None if the target is not part of the event (e.g. it was deleted or is not
+    /// visible to the current user).
+    pub fn target<'e>(&self, event: &'e EventFull) -> Option<ReferenceTarget<'e>> {
+        let attribute = || {
+            event
+                .attribute_by_uuid(self.referenced_uuid)
+                .map(ReferenceTarget::Attribute)
+        };
+        let object = || {
+            event
+                .object_by_uuid(self.referenced_uuid)
+                .map(ReferenceTarget::Object)
+        };
+        match self.referenced_type {
+            ReferencedType::Attribute => attribute(),
+            ReferencedType::Object => object(),
+            ReferencedType::Unsupported(_) => object().or_else(attribute),
+        }
+    }
+}
+
+impl ObjectUpdate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distribution(&mut self, distribution: Distribution) -> &mut Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    pub fn comment(&mut self, comment: impl Into<String>) -> &mut Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Adds an attribute to the object. The attribute's object_relation is set to `relation`.
+    pub fn add_attribute(
+        &mut self,
+        relation: impl Into<String>,
+        mut attribute: NewAttribute,
+    ) -> &mut Self {
+        attribute.relation(relation);
+        self.attributes.push(EntityChange::Add(attribute));
+        self
+    }
+
+    /// Removes the attribute with the given uuid from the object.
+    pub fn remove_attribute(&mut self, uuid: Uuid) -> &mut Self {
+        self.attributes.push(EntityChange::Remove(uuid));
+        self
+    }
+}
+
 impl ObjectFull {
     pub fn attributes(&self) -> &Vec<AttributeFull> {
         &self.attributes
     }
 
+    /// Returns the tags attached to the object itself. MISP usually attaches tags to the
+    /// attributes of an object rather than to the object, see
+    /// [`has_tag_on_attribute`](#method.has_tag_on_attribute).
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    /// Returns true if a tag with the given name is attached to the object itself.
+    pub fn has_tag(&self, name: impl AsRef<str>) -> bool {
+        find_tag(&self.tags, name.as_ref()).is_some()
+    }
+
+    /// Returns true if a tag with the given name is attached to the object or to one of its
+    /// attributes.
+    pub fn has_tag_on_attribute(&self, name: impl AsRef<str>) -> bool {
+        let name = name.as_ref();
+        self.has_tag(name) || self.attributes.iter().any(|a| a.has_tag(name))
+    }
+
+    /// Returns the references from this object to other objects or attributes.
+    pub fn references(&self) -> &Vec<ObjectReference> {
+        &self.references
+    }
+
+    /// Follows all the references of this object. `event` has to be the event owning the object.
+    /// References whose target is not part of the event are skipped.
+    pub fn referenced<'a, 'e>(
+        &'a self,
+        event: &'e EventFull,
+    ) -> impl Iterator<Item = (&'a ObjectReference, ReferenceTarget<'e>)> {
+        self.references
+            .iter()
+            .filter_map(move |r| r.target(event).map(|target| (r, target)))
+    }
+
+    /// Returns the objects of `event` that have a reference pointing to this object.
+    pub fn referenced_by<'e>(
+        &self,
+        event: &'e EventFull,
+    ) -> impl Iterator<Item = (&'e ObjectFull, &'e ObjectReference)> {
+        let uuid = self.uuid();
+        event.objects().iter().flat_map(move |o| {
+            o.references()
+                .iter()
+                .filter(move |r| r.referenced_uuid() == uuid)
+                .map(move |r| (o, r))
+        })
+    }
+
     /// A shortcut function to access an attribute of an object that has a specific object_relation.
     pub fn attribute(&self, str: impl AsRef<str>) -> Option<&AttributeFull> {
         self.attributes()
@@ -113,10 +788,34 @@ impl ObjectFull {
     // This is needed for easier access, so that you can use ObjectFull.name() instead of
     // ObjectFull.object.date()
     //
+    pub fn id(&self) -> ObjectIdentifier {
+        self.object.id()
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.object.uuid()
+    }
+
+    pub fn event_identifier(&self) -> EventIdentifier {
+        self.object.event_identifier()
+    }
+
     pub fn name(&self) -> &str {
         self.object.name()
     }
 
+    pub fn meta_category(&self) -> &str {
+        self.object.meta_category()
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        self.object.timestamp()
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.object.deleted()
+    }
+
     pub fn description(&self) -> &str {
         self.object.description()
     }
@@ -125,3 +824,87 @@ impl ObjectFull {
         self.object.comment()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::event::tests::sample_event;
+    use crate::object::{NewObject, ReferenceTarget, ReferencedType, UnknownObjectRelation};
+    use serde_json::json;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn templated_object_to_json() {
+        let mut object = NewObject::file("invoice.pdf.exe");
+        object
+            .add_value(
+                "sha256",
+                "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            )
+            .unwrap();
+
+        assert_eq!(
+            json!({
+                "name": "file",
+                "meta-category": "file",
+                "template_uuid": "688c46fb-5edb-40a3-8273-1af7923e2215",
+                "Attribute": [
+                    {"type": "filename", "value": "invoice.pdf.exe", "object_relation": "filename"},
+                    {
+                        "type": "sha256",
+                        "value": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+                        "object_relation": "sha256"
+                    }
+                ]
+            }),
+            serde_json::to_value(&object).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn unknown_template_relation() {
+        assert_eq!(
+            UnknownObjectRelation {
+                template: "url".into(),
+                relation: "sha256".into()
+            },
+            NewObject::url("https://evil.example/")
+                .add_value("sha256", "abc")
+                .unwrap_err()
+        );
+        assert!(NewObject::new("custom").add_value("foo", "bar").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn follow_object_references() {
+        let event = sample_event();
+        let dropper = &event.objects()[0];
+        let payload = &event.objects()[1];
+
+        assert_eq!(2, dropper.references().len());
+        assert_eq!(
+            &ReferencedType::Object,
+            dropper.references()[0].referenced_type()
+        );
+
+        let targets: Vec<_> = dropper.referenced(&event).collect();
+        assert_eq!(2, targets.len());
+        match targets[0] {
+            (reference, ReferenceTarget::Object(object)) => {
+                assert_eq!("drops", reference.relationship_type());
+                assert_eq!(payload.uuid(), object.uuid());
+            }
+            _ => panic!("Expected the first reference to point to an object"),
+        }
+        match targets[1] {
+            (_, ReferenceTarget::Attribute(attribute)) => {
+                assert_eq!("198.51.100.7", attribute.value())
+            }
+            _ => panic!("Expected the second reference to point to an attribute"),
+        }
+
+        let referencing: Vec<_> = payload.referenced_by(&event).collect();
+        assert_eq!(1, referencing.len());
+        assert_eq!(dropper.uuid(), referencing[0].0.uuid());
+    }
+}
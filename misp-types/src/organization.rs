@@ -68,6 +68,20 @@ impl fmt::Display for OrganizationIdentifier {
     }
 }
 
+impl OrganizationTemporary {
+    pub fn id(&self) -> OrganizationIdentifier {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+}
+
 impl Serialize for GenericOrganizationIdentifier {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
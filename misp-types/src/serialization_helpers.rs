@@ -138,3 +138,246 @@ pub mod number_embedded_in_string {
             .map_err(|_| serde::de::Error::custom("Expected json number embedded in string"))
     }
 }
+
+/// Serializes a list of tag names the way MISP expects them when creating entities:
+/// `[{"name": "tlp:white"}, {"name": "osint"}]`
+#[cfg(feature = "serde")]
+pub mod tag_names {
+    use serde::{self, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct NamedTag<'a> {
+        name: &'a str,
+    }
+
+    pub fn serialize<S>(names: &[String], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(names.iter().map(|name| NamedTag { name }))
+    }
+}
+
+/// MISP is not consistent in how it represents booleans: depending on the endpoint they are sent as
+/// `true`, `1`, `"1"` or even as a message string (e.g. `"success": "Tag(s) attached."`).
+/// Anything that is not `false`, `0`, `null` or an empty/zero string is considered `true`.
+#[cfg(feature = "serde")]
+pub mod lenient_bool {
+    use serde::{self, Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S>(b: &bool, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bool(*b)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<bool, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Value::deserialize(deserializer)? {
+            Value::Null => false,
+            Value::Bool(b) => b,
+            Value::Number(n) => n.as_f64() != Some(0.0),
+            Value::String(s) => !(s.is_empty() || s == "0" || s == "false"),
+            Value::Array(_) | Value::Object(_) => true,
+        })
+    }
+}
+
+/// An optional number that is either sent as JSON number, as number embedded in a string, or null.
+#[cfg(feature = "serde")]
+pub mod option_number_embedded_in_string {
+    use serde::{self, Deserialize, Deserializer, Serializer};
+    use serde_json::Value;
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn serialize<S, T>(number: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        match number {
+            Some(number) => serializer.serialize_str(&number.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+    {
+        let text = match Value::deserialize(deserializer)? {
+            Value::Null => return Ok(None),
+            Value::String(s) if s.is_empty() => return Ok(None),
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            _ => return Err(serde::de::Error::custom("Expected a number or null")),
+        };
+        text.parse()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("Expected json number embedded in string"))
+    }
+}
+
+/// A value that the server sends as null when it is empty (e.g. an empty list). Null is mapped to
+/// the default value of the type.
+#[cfg(feature = "serde")]
+pub mod null_as_default {
+    use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Default,
+    {
+        Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+    }
+}
+
+/// The `meta` key/values of a galaxy cluster. Depending on the key, the server sends a single
+/// string or a list of values. Every value is mapped to a list of strings.
+#[cfg(feature = "serde")]
+pub mod galaxy_meta {
+    use serde::{self, Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S>(
+        meta: &BTreeMap<String, Vec<String>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        meta.serialize(serializer)
+    }
+
+    fn to_text(value: Value) -> String {
+        match value {
+            Value::String(s) => s,
+            other => other.to_string(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BTreeMap<String, Vec<String>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // An empty meta is sent as empty list instead of an empty object
+        let meta = match Value::deserialize(deserializer)? {
+            Value::Object(map) => map,
+            Value::Null | Value::Array(_) => return Ok(BTreeMap::new()),
+            _ => return Err(serde::de::Error::custom("Expected a map of meta values")),
+        };
+        Ok(meta
+            .into_iter()
+            .map(|(key, value)| {
+                let values = match value {
+                    Value::Null => Vec::new(),
+                    Value::Array(values) => values.into_iter().map(to_text).collect(),
+                    value => vec![to_text(value)],
+                };
+                (key, values)
+            })
+            .collect())
+    }
+}
+
+/// A relative duration, as used by the `last` filter of searches (e.g. `7d` or `12h`). Durations
+/// are written with the largest unit that represents them exactly. Fractions of seconds are
+/// dropped, and durations shorter than one second cannot be serialized.
+#[cfg(feature = "serde")]
+pub mod option_duration_to_relative {
+    use serde::{self, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    const UNITS: [(char, u64); 4] = [('d', 86400), ('h', 3600), ('m', 60), ('s', 1)];
+
+    pub fn serialize<S>(option: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match option {
+            Some(duration) => {
+                let seconds = duration.as_secs();
+                // MISP would read `0d` as no filter at all
+                if seconds == 0 {
+                    return Err(serde::ser::Error::custom(
+                        "A relative duration must be at least one second",
+                    ));
+                }
+                let (unit, size) = UNITS
+                    .iter()
+                    .find(|(_, size)| seconds % size == 0)
+                    .copied()
+                    .unwrap_or(('s', 1));
+                serializer.serialize_str(&format!("{}{}", seconds / size, unit))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let temp: Option<String> = Option::deserialize(deserializer)?;
+        match temp {
+            Some(text) => {
+                let unit = text.chars().last().unwrap_or('s');
+                let (number, size) = match UNITS.iter().find(|(u, _)| *u == unit) {
+                    Some((_, size)) => (&text[..text.len() - 1], *size),
+                    None => (&text[..], 1),
+                };
+                let v = number
+                    .parse::<u64>()
+                    .map_err(|_| serde::de::Error::custom("Wrong relative duration format"))?;
+                let seconds = v
+                    .checked_mul(size)
+                    .ok_or_else(|| serde::de::Error::custom("Relative duration is too long"))?;
+                Ok(Some(Duration::from_secs(seconds)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "json-using-serde"))]
+mod tests {
+    use super::option_duration_to_relative;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Last(#[serde(with = "option_duration_to_relative")] Option<Duration>);
+
+    #[test]
+    fn relative_durations() {
+        let json = |seconds: u64| serde_json::to_string(&Last(Some(Duration::from_secs(seconds))));
+        assert_eq!(r#""7d""#, json(7 * 86400).unwrap());
+        assert_eq!(r#""90m""#, json(5400).unwrap());
+        assert_eq!(r#""61s""#, json(61).unwrap());
+        assert!(json(0).is_err());
+        assert!(serde_json::to_string(&Last(Some(Duration::from_millis(500)))).is_err());
+
+        assert_eq!(
+            Last(Some(Duration::from_secs(12 * 3600))),
+            serde_json::from_str(r#""12h""#).unwrap()
+        );
+        assert!(serde_json::from_str::<Last>(r#""999999999999999999d""#).is_err());
+        assert!(serde_json::from_str::<Last>(r#""7w""#).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::attribute::AttributeIdentifier;
+use crate::attribute_type::{AttributeCategory, AttributeType};
+use crate::attribute_value::{AttributeValue, AttributeValueError};
+use crate::event::EventIdentifier;
+use crate::organization::{OrganizationIdentifier, OrganizationTemporary};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use uuid::Uuid;
+
+#[cfg(feature = "serde")]
+use super::serialization_helpers::{datetime_to_epoch, lenient_bool, number_embedded_in_string};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ShadowAttributeIdentifier(pub u64);
+
+/// A proposal made by another organization: either a new attribute for an event, a change to an
+/// existing attribute or the request to delete an existing attribute. Proposals have to be
+/// accepted by the owner of the event before they are applied.
+/// [RFC](https://github.com/MISP/misp-rfc/blob/master/misp-core-format/raw.md#shadowattribute)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShadowAttribute {
+    id: ShadowAttributeIdentifier,
+    uuid: Uuid,
+    event_id: EventIdentifier,
+    #[serde(default)]
+    event_uuid: Option<Uuid>,
+    org_id: OrganizationIdentifier,
+    #[serde(with = "number_embedded_in_string")]
+    old_id: u64,
+    #[serde(rename = "type")]
+    kind: AttributeType,
+    category: AttributeCategory,
+    value: String,
+    #[serde(with = "lenient_bool")]
+    to_ids: bool,
+    #[serde(default)]
+    comment: String,
+    #[serde(with = "datetime_to_epoch")]
+    timestamp: DateTime<Utc>,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    deleted: bool,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    proposal_to_delete: bool,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    disable_correlation: bool,
+    /// The email address of the user that made the proposal.
+    #[serde(default)]
+    email: String,
+    #[serde(rename = "Org")]
+    #[serde(default)]
+    org: Option<OrganizationTemporary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShadowAttributeEmbedded {
+    #[serde(rename = "ShadowAttribute")]
+    pub shadow_attribute: ShadowAttribute,
+}
+
+impl Serialize for ShadowAttributeIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for ShadowAttributeIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number_embedded_in_string::deserialize(deserializer).map(|v| ShadowAttributeIdentifier(v))
+    }
+}
+
+impl fmt::Display for ShadowAttributeIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Into<ShadowAttributeIdentifier> for u64 {
+    fn into(self) -> ShadowAttributeIdentifier {
+        ShadowAttributeIdentifier(self)
+    }
+}
+
+impl Into<ShadowAttributeIdentifier> for &ShadowAttribute {
+    fn into(self) -> ShadowAttributeIdentifier {
+        self.id
+    }
+}
+
+impl ShadowAttribute {
+    pub fn id(&self) -> ShadowAttributeIdentifier {
+        self.id
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    pub fn event_identifier(&self) -> EventIdentifier {
+        self.event_id
+    }
+
+    pub fn event_uuid(&self) -> Option<Uuid> {
+        self.event_uuid
+    }
+
+    /// The identifier of the organization that made the proposal.
+    pub fn organization_identifier(&self) -> OrganizationIdentifier {
+        self.org_id
+    }
+
+    /// The organization that made the proposal, if it was sent along by the server.
+    pub fn organization(&self) -> Option<&OrganizationTemporary> {
+        self.org.as_ref()
+    }
+
+    /// The attribute the proposal changes or deletes. None if the proposal is a new attribute.
+    pub fn attribute_identifier(&self) -> Option<AttributeIdentifier> {
+        match self.old_id {
+            0 => None,
+            id => Some(AttributeIdentifier(id)),
+        }
+    }
+
+    /// Returns true if the proposal adds a new attribute to the event.
+    pub fn is_new_attribute(&self) -> bool {
+        self.old_id == 0
+    }
+
+    /// Returns true if the proposal asks to delete the existing attribute.
+    pub fn proposal_to_delete(&self) -> bool {
+        self.proposal_to_delete
+    }
+
+    pub fn kind(&self) -> &AttributeType {
+        &self.kind
+    }
+
+    pub fn category(&self) -> &AttributeCategory {
+        &self.category
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Parses the proposed value according to its type.
+    pub fn value_parsed(&self) -> Result<AttributeValue, AttributeValueError> {
+        AttributeValue::parse(&self.kind, &self.value)
+    }
+
+    pub fn to_ids(&self) -> bool {
+        self.to_ids
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn disable_correlation(&self) -> bool {
+        self.disable_correlation
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shadow_attribute::ShadowAttribute;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn json_to_shadow_attribute() {
+        let proposal: ShadowAttribute = serde_json::from_str(
+            r#"{
+              "id": "5", "org_id": "2", "event_id": "7", "type": "ip-dst",
+              "category": "Network activity", "value": "198.51.100.9", "to_ids": true,
+              "uuid": "5f2c5d1e-0000-4000-8000-000000000051",
+              "event_uuid": "5f2c5d1e-0000-4000-8000-000000000001",
+              "deleted": false, "timestamp": "1596744000", "proposal_to_delete": false,
+              "disable_correlation": false, "first_seen": null, "last_seen": null,
+              "comment": "Typo in the last octet", "event_org_id": "1", "old_id": "11",
+              "email": "analyst@partner.example",
+              "Org": {"id": "2", "name": "Partner", "uuid": "5f2c5d1e-0000-4000-8000-000000000002"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(5, proposal.id().0);
+        assert_eq!(Some(11), proposal.attribute_identifier().map(|a| a.0));
+        assert!(!proposal.is_new_attribute());
+        assert_eq!("198.51.100.9", proposal.value());
+        assert_eq!("Partner", proposal.organization().unwrap().name());
+    }
+}
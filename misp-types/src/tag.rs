@@ -0,0 +1,285 @@
+use crate::machine_tag::{MachineTag, MachineTagError};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use super::serialization_helpers::{
+    lenient_bool, number_embedded_in_string, option_number_embedded_in_string,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TagIdentifier(pub u64);
+
+/// A tag attached to an event or an attribute (e.g. `tlp:amber`).
+/// [RFC](https://github.com/MISP/misp-rfc/blob/master/misp-core-format/raw.md#tag)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tag {
+    id: TagIdentifier,
+    name: String,
+    #[serde(default)]
+    colour: String,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    exportable: bool,
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    hide_tag: bool,
+    #[serde(default)]
+    #[serde(with = "option_number_embedded_in_string")]
+    numerical_value: Option<i64>,
+    /// Local tags are only visible on the instance they were attached on and are never
+    /// synchronised to other instances.
+    #[serde(default)]
+    #[serde(with = "lenient_bool")]
+    local: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagEmbedded {
+    #[serde(rename = "Tag")]
+    pub tag: Tag,
+}
+
+/// Identifies a tag either by its id or by its name.
+#[derive(Debug, Clone)]
+pub enum GenericTagIdentifier {
+    Local(TagIdentifier),
+    /// The name of a tag. MISP treats a name that only consists of digits as an id, so such a tag
+    /// cannot be identified by its name.
+    Named(String),
+}
+
+/// A tag that does not exist on the server yet.
+///
+/// Optional fields that are not set are left out of the JSON, so that the server uses its
+/// defaults.
+#[derive(Serialize, Debug, Clone)]
+pub struct NewTag {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    colour: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exportable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hide_tag: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    numerical_value: Option<i64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NewTagEmbedded {
+    #[serde(rename = "Tag")]
+    pub tag: NewTag,
+}
+
+impl Serialize for TagIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TagIdentifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        number_embedded_in_string::deserialize(deserializer).map(|v| TagIdentifier(v))
+    }
+}
+
+impl fmt::Display for TagIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Tag {
+    pub fn id(&self) -> TagIdentifier {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the colour of the tag as hex code (e.g. `#ffc000`).
+    pub fn colour(&self) -> &str {
+        &self.colour
+    }
+
+    pub fn exportable(&self) -> bool {
+        self.exportable
+    }
+
+    pub fn hide_tag(&self) -> bool {
+        self.hide_tag
+    }
+
+    /// The numerical value of the tag, used to score entities (e.g. for taxonomies such as
+    /// `admiralty-scale`).
+    pub fn numerical_value(&self) -> Option<i64> {
+        self.numerical_value
+    }
+
+    pub fn local(&self) -> bool {
+        self.local
+    }
+
+    /// Parses the name of the tag as machine tag (`namespace:predicate="value"`). Fails for tags
+    /// that do not follow the machine tag syntax, such as free-text tags.
+    pub fn machine_tag(&self) -> Result<MachineTag, MachineTagError> {
+        self.name.parse()
+    }
+
+    /// Returns true if the tag has the given name. Tag names are compared case insensitively,
+    /// like MISP does.
+    pub fn is(&self, name: impl AsRef<str>) -> bool {
+        self.name.eq_ignore_ascii_case(name.as_ref())
+    }
+}
+
+impl NewTag {
+    /// Creates a new tag with the given name (e.g. `tlp:amber`).
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            colour: None,
+            exportable: None,
+            hide_tag: None,
+            numerical_value: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the colour of the tag as hex code (e.g. `#ffc000`). If not set, the server picks a
+    /// random colour.
+    pub fn colour(&mut self, colour: impl Into<String>) -> &mut Self {
+        self.colour = Some(colour.into());
+        self
+    }
+
+    pub fn exportable(&mut self, exportable: bool) -> &mut Self {
+        self.exportable = Some(exportable);
+        self
+    }
+
+    pub fn hide_tag(&mut self, hide_tag: bool) -> &mut Self {
+        self.hide_tag = Some(hide_tag);
+        self
+    }
+
+    pub fn numerical_value(&mut self, numerical_value: i64) -> &mut Self {
+        self.numerical_value = Some(numerical_value);
+        self
+    }
+}
+
+impl Serialize for GenericTagIdentifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_url_id())
+    }
+}
+
+impl GenericTagIdentifier {
+    pub fn to_url_id(&self) -> String {
+        match self {
+            GenericTagIdentifier::Local(id) => id.to_string(),
+            GenericTagIdentifier::Named(name) => name.clone(),
+        }
+    }
+}
+
+impl Into<GenericTagIdentifier> for u64 {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Local(TagIdentifier(self))
+    }
+}
+
+impl Into<GenericTagIdentifier> for TagIdentifier {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Local(self)
+    }
+}
+
+impl Into<GenericTagIdentifier> for &str {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Named(self.to_string())
+    }
+}
+
+impl Into<GenericTagIdentifier> for String {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Named(self)
+    }
+}
+
+impl Into<GenericTagIdentifier> for &Tag {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Local(self.id)
+    }
+}
+
+impl Into<GenericTagIdentifier> for Tag {
+    fn into(self) -> GenericTagIdentifier {
+        GenericTagIdentifier::Local(self.id)
+    }
+}
+
+/// Finds a tag by its name in a list of tags.
+pub(crate) fn find_tag<'a>(tags: &'a [Tag], name: &str) -> Option<&'a Tag> {
+    tags.iter().find(|t| t.is(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tag::{NewTag, NewTagEmbedded, Tag};
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn json_to_tag() {
+        let tag: Tag = serde_json::from_str(
+            r##"{"id": "4", "name": "admiralty-scale:source-reliability=\"b\"", "colour": "#0088cc",
+                "exportable": true, "user_id": "0", "hide_tag": false, "numerical_value": "75",
+                "is_galaxy": false, "is_custom_galaxy": false, "local": 0}"##,
+        )
+        .unwrap();
+        assert_eq!(4, tag.id().0);
+        assert_eq!("#0088cc", tag.colour());
+        assert!(tag.exportable());
+        assert_eq!(Some(75), tag.numerical_value());
+        assert!(!tag.local());
+        assert!(tag
+            .machine_tag()
+            .unwrap()
+            .is("admiralty-scale", "source-reliability"));
+
+        let tag: Tag = serde_json::from_str(
+            r#"{"id": "1", "name": "tlp:red", "numerical_value": null, "local": 1}"#,
+        )
+        .unwrap();
+        assert!(tag.is("TLP:RED"));
+        assert_eq!(None, tag.numerical_value());
+        assert!(tag.local());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn new_tag_to_json() {
+        let mut tag = NewTag::new("campaign:operation-x");
+        tag.colour("#ff0000").exportable(false);
+        assert_eq!(
+            serde_json::json!({"Tag": {"name": "campaign:operation-x", "colour": "#ff0000", "exportable": false}}),
+            serde_json::to_value(NewTagEmbedded { tag }).unwrap()
+        );
+    }
+}
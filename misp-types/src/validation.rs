@@ -0,0 +1,328 @@
+//! Validation and normalisation of attribute values, following the rules the MISP server applies
+//! when an attribute is saved.
+//!
+//! Normalising values before submitting them keeps local deduplication consistent with the
+//! server's and catches invalid values before the server rejects the whole event.
+use crate::attribute_type::AttributeType;
+use crate::attribute_value::{strip_as_prefix, AttributeValue, AttributeValueError, HashAlgorithm};
+use ipnet::IpNet;
+use std::error::Error;
+use std::fmt;
+use std::net::IpAddr;
+
+/// The rule an attribute value did not satisfy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationRule {
+    /// The value is empty or only contains whitespace.
+    NotEmpty,
+    /// The hash does not have the length of its algorithm.
+    HashLength {
+        algorithm: HashAlgorithm,
+        expected: usize,
+    },
+    /// The hash contains characters that are not hexadecimal, or a fuzzy hash contains
+    /// whitespace.
+    HashCharacters(HashAlgorithm),
+    IpAddress,
+    Port,
+    AsNumber,
+    Domain,
+    Url,
+    Email,
+    /// The value of a composite type (e.g. `domain|ip`) does not contain a `|`.
+    CompositeSeparator,
+}
+
+/// The error returned when a value does not match the rules of its attribute type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    kind: AttributeType,
+    value: String,
+    rule: ValidationRule,
+}
+
+impl ValidationError {
+    pub fn kind(&self) -> &AttributeType {
+        &self.kind
+    }
+
+    /// The value as it was given, before normalisation.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn rule(&self) -> &ValidationRule {
+        &self.rule
+    }
+}
+
+impl From<&AttributeValueError> for ValidationRule {
+    fn from(error: &AttributeValueError) -> Self {
+        match error {
+            AttributeValueError::InvalidIpAddress(_) => ValidationRule::IpAddress,
+            AttributeValueError::InvalidPort(_) => ValidationRule::Port,
+            AttributeValueError::InvalidAsNumber(_) => ValidationRule::AsNumber,
+            AttributeValueError::InvalidHash { algorithm, value } => match algorithm.hex_length() {
+                Some(expected) if value.len() != expected => ValidationRule::HashLength {
+                    algorithm: *algorithm,
+                    expected,
+                },
+                _ => ValidationRule::HashCharacters(*algorithm),
+            },
+            AttributeValueError::InvalidDomain(_) => ValidationRule::Domain,
+            AttributeValueError::InvalidUrl { .. } => ValidationRule::Url,
+            AttributeValueError::InvalidEmail(_) => ValidationRule::Email,
+            AttributeValueError::MissingSeparator(_) => ValidationRule::CompositeSeparator,
+        }
+    }
+}
+
+/// Rewrites a value the way the server stores it. Values that cannot be parsed are returned
+/// unchanged; they are rejected by the validation afterwards.
+fn normalize_unchecked(kind: &AttributeType, value: &str) -> String {
+    use AttributeType::*;
+    if let Some(algorithm) = HashAlgorithm::for_type(kind) {
+        return match value.rfind('|') {
+            Some(i) if kind.as_str().starts_with("filename|") => format!(
+                "{}|{}",
+                &value[..i],
+                normalize_hash(kind, algorithm, &value[i + 1..])
+            ),
+            _ => normalize_hash(kind, algorithm, value),
+        };
+    }
+    match kind {
+        IpSrc | IpDst => normalize_ip(value),
+        IpSrcPort | IpDstPort => map_composite(value, normalize_ip, normalize_number),
+        Domain | Hostname => normalize_domain(value),
+        DomainIp => map_composite(value, normalize_domain, normalize_ip),
+        HostnamePort => map_composite(value, normalize_domain, normalize_number),
+        Email | EmailSrc | EmailDst | EmailReplyTo | TargetEmail | WhoisRegistrantEmail
+        | DnsSoaEmail => match value.rfind('@') {
+            // The local part of an address may be case sensitive, the domain is not
+            Some(i) => format!("{}@{}", &value[..i], normalize_domain(&value[i + 1..])),
+            None => value.to_string(),
+        },
+        Port => normalize_number(value),
+        As => normalize_number(strip_as_prefix(value)),
+        _ => value.to_string(),
+    }
+}
+
+/// Lowercases hashes with a fixed length and removes the colons of x509 fingerprints. Fuzzy
+/// hashes are case sensitive and kept as they are.
+fn normalize_hash(kind: &AttributeType, algorithm: HashAlgorithm, value: &str) -> String {
+    let value = value.trim();
+    if algorithm.hex_length().is_none() {
+        return value.to_string();
+    }
+    if kind.as_str().starts_with("x509-fingerprint-") {
+        value.replace(':', "").to_lowercase()
+    } else {
+        value.to_lowercase()
+    }
+}
+
+/// Writes addresses and networks in their canonical form, which compresses IPv6 addresses
+/// (e.g. `2001:0DB8:0:0::1` becomes `2001:db8::1`).
+fn normalize_ip(value: &str) -> String {
+    if value.contains('/') {
+        match value.parse::<IpNet>() {
+            Ok(network) => network.to_string(),
+            Err(_) => value.to_string(),
+        }
+    } else {
+        match value.parse::<IpAddr>() {
+            Ok(ip) => ip.to_string(),
+            Err(_) => value.to_string(),
+        }
+    }
+}
+
+/// Lowercases domains and removes the trailing dot of fully qualified names.
+fn normalize_domain(value: &str) -> String {
+    value.strip_suffix('.').unwrap_or(value).to_lowercase()
+}
+
+/// Removes leading zeros.
+fn normalize_number(value: &str) -> String {
+    match value.parse::<u64>() {
+        Ok(number) => number.to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+fn map_composite(value: &str, first: fn(&str) -> String, second: fn(&str) -> String) -> String {
+    match value.find('|') {
+        Some(i) => format!("{}|{}", first(&value[..i]), second(&value[i + 1..])),
+        None => value.to_string(),
+    }
+}
+
+/// Normalises a value according to the rules of its attribute type and checks that the result is
+/// valid. Returns the normalised value.
+///
+/// # Examples
+///
+/// ```
+/// # use misp_types::attribute_type::AttributeType;
+/// # use misp_types::validation::{normalize, ValidationRule};
+/// assert_eq!(
+///     "2001:db8::1",
+///     normalize(&AttributeType::IpDst, " 2001:0DB8:0:0::1 ").unwrap()
+/// );
+/// assert_eq!(
+///     "evil.example",
+///     normalize(&AttributeType::Domain, "Evil.Example.").unwrap()
+/// );
+/// let error = normalize(&AttributeType::Md5, "d41d8cd98f00b204").unwrap_err();
+/// assert!(matches!(error.rule(), ValidationRule::HashLength { expected: 32, .. }));
+/// ```
+pub fn normalize(kind: &AttributeType, value: &str) -> Result<String, ValidationError> {
+    let error = |rule| ValidationError {
+        kind: kind.clone(),
+        value: value.to_string(),
+        rule,
+    };
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(error(ValidationRule::NotEmpty));
+    }
+    let normalized = normalize_unchecked(kind, trimmed);
+    match AttributeValue::parse(kind, &normalized) {
+        Ok(_) => Ok(normalized),
+        Err(e) => Err(error(ValidationRule::from(&e))),
+    }
+}
+
+/// Checks that a value satisfies the rules of its attribute type. Values that only differ from
+/// their normalised form (e.g. an uppercase hash) are valid.
+pub fn validate(kind: &AttributeType, value: &str) -> Result<(), ValidationError> {
+    normalize(kind, value).map(|_| ())
+}
+
+impl fmt::Display for ValidationRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationRule::NotEmpty => write!(f, "the value must not be empty"),
+            ValidationRule::HashLength {
+                algorithm,
+                expected,
+            } => write!(
+                f,
+                "a {:?} hash must be {} characters long",
+                algorithm, expected
+            ),
+            ValidationRule::HashCharacters(algorithm) => {
+                write!(f, "the {:?} hash contains invalid characters", algorithm)
+            }
+            ValidationRule::IpAddress => write!(f, "expected an IP address or network"),
+            ValidationRule::Port => write!(f, "expected a port between 0 and 65535"),
+            ValidationRule::AsNumber => write!(f, "expected an AS number"),
+            ValidationRule::Domain => write!(f, "expected a domain name"),
+            ValidationRule::Url => write!(f, "expected a URL"),
+            ValidationRule::Email => write!(f, "expected an email address"),
+            ValidationRule::CompositeSeparator => {
+                write!(f, "the parts of the value must be separated by '|'")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid value '{}' for attribute type '{}': {}",
+            self.value, self.kind, self.rule
+        )
+    }
+}
+
+impl Error for ValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute_type::AttributeType;
+    use crate::attribute_value::HashAlgorithm;
+    use crate::validation::{normalize, ValidationRule};
+
+    fn normalized(kind: &str, value: &str) -> String {
+        normalize(&kind.parse::<AttributeType>().unwrap(), value).unwrap()
+    }
+
+    fn failed_rule(kind: &str, value: &str) -> ValidationRule {
+        normalize(&kind.parse::<AttributeType>().unwrap(), value)
+            .unwrap_err()
+            .rule()
+            .clone()
+    }
+
+    #[test]
+    pub fn normalize_values() {
+        assert_eq!(
+            "d41d8cd98f00b204e9800998ecf8427e",
+            normalized("md5", "D41D8CD98F00B204E9800998ECF8427E")
+        );
+        assert_eq!(
+            "Invoice.PDF.exe|d41d8cd98f00b204e9800998ecf8427e",
+            normalized(
+                "filename|md5",
+                "Invoice.PDF.exe|D41D8CD98F00B204E9800998ECF8427E"
+            )
+        );
+        assert_eq!(
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            normalized(
+                "x509-fingerprint-sha1",
+                "DA:39:A3:EE:5E:6B:4B:0D:32:55:BF:EF:95:60:18:90:AF:D8:07:09"
+            )
+        );
+        assert_eq!(
+            "3:AXGBicFlgVNhBGcL6wCrFQEv:AXGHsNhxLsr2C",
+            normalized("ssdeep", "3:AXGBicFlgVNhBGcL6wCrFQEv:AXGHsNhxLsr2C")
+        );
+        assert_eq!("2001:db8::/32", normalized("ip-src", "2001:0db8::/32"));
+        assert_eq!(
+            "2001:db8::1|443",
+            normalized("ip-dst|port", "2001:db8:0::1|0443")
+        );
+        assert_eq!(
+            "evil.example|198.51.100.7",
+            normalized("domain|ip", "EVIL.example.|198.51.100.7")
+        );
+        assert_eq!(
+            "Phish@evil.example",
+            normalized("email-src", "Phish@Evil.Example")
+        );
+        assert_eq!("64496", normalized("AS", "AS64496"));
+        assert_eq!("Some comment", normalized("text", "  Some comment "));
+    }
+
+    #[test]
+    pub fn report_failed_rule() {
+        assert_eq!(ValidationRule::NotEmpty, failed_rule("text", "  "));
+        assert_eq!(
+            ValidationRule::HashLength {
+                algorithm: HashAlgorithm::Sha1,
+                expected: 40
+            },
+            failed_rule("sha1", "d41d8cd98f00b204e9800998ecf8427e")
+        );
+        assert_eq!(
+            ValidationRule::HashCharacters(HashAlgorithm::Md5),
+            failed_rule("md5", "z41d8cd98f00b204e9800998ecf8427e")
+        );
+        assert_eq!(
+            ValidationRule::IpAddress,
+            failed_rule("ip-dst", "198.51.100")
+        );
+        assert_eq!(ValidationRule::Domain, failed_rule("hostname", "localhost"));
+        assert_eq!(
+            ValidationRule::CompositeSeparator,
+            failed_rule("filename|sha256", "invoice.exe")
+        );
+        assert_eq!(ValidationRule::Port, failed_rule("port", "65536"));
+        assert_eq!(ValidationRule::AsNumber, failed_rule("AS", "€1"));
+    }
+}